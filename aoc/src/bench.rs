@@ -0,0 +1,272 @@
+use aoc_common::bench::{self, Baseline, Config, Stats};
+use aoc_common::format;
+use aoc_common::input;
+use aoc_common::output::{ColorChoice, Output, State};
+use aoc_common::solution::{Registry, Solution};
+use clap::Args;
+use std::fs;
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Year to benchmark, all years are used when omitted
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// Day to benchmark, all days are used when omitted
+    #[arg(long)]
+    pub day: Option<u8>,
+
+    /// Part to benchmark, both parts are used when omitted
+    #[arg(long)]
+    pub part: Option<u8>,
+
+    /// Benchmark with the demo input instead of the real one
+    #[arg(long)]
+    pub demo: bool,
+
+    /// Runs of each solution before measuring
+    #[arg(long, default_value_t = 3)]
+    pub warmup: usize,
+
+    /// Measured runs of each solution
+    #[arg(long, default_value_t = 20)]
+    pub iterations: usize,
+
+    /// Baseline file the results are compared with
+    #[arg(long, default_value = "bench-baseline.json")]
+    pub baseline: String,
+
+    /// Save the results to the baseline file
+    #[arg(long)]
+    pub save: bool,
+
+    /// Also run the answers marked with `ignore` in answers.toml
+    #[arg(long)]
+    pub include_ignored: bool,
+
+    /// When to use colors
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+}
+
+// Changes of the median within this percentage are shown as noise
+const NOISE: f64 = 5.0;
+
+// Result of benchmarking one solution
+struct Row<'a> {
+    solution: &'a Solution,
+    result: Option<Result<Stats, String>>,
+    baseline: Option<Stats>,
+}
+
+fn bench_solution(
+    registry: &Registry,
+    solution: &Solution,
+    args: &BenchArgs,
+) -> Option<Result<Stats, String>> {
+    let ignored = registry
+        .answers
+        .get(solution.day, solution.part, args.demo)
+        .is_some_and(|e| e.ignored(solution.version).is_some());
+
+    if ignored && !args.include_ignored {
+        return None;
+    }
+
+    let input = match input::load(registry.src_dir, solution.day, solution.part, args.demo) {
+        Ok(input) => input,
+        Err(err) => return Some(Err(format!("could not read input: {}", err))),
+    };
+
+    let config = Config {
+        warmup: args.warmup,
+        iterations: args.iterations.max(1),
+    };
+
+    Some(bench::measure(solution, &input, &config))
+}
+
+// Benchmarks the selected solutions printing their stats and the change from
+// the baseline. Returns false if any solution failed.
+pub fn run(registries: &[Registry], args: &BenchArgs) -> bool {
+    let output = Output::new(args.color);
+
+    let mut baseline = match Baseline::load(&args.baseline) {
+        Ok(baseline) => baseline,
+        Err(err) => {
+            eprintln!("Could not load baseline {}: {}", args.baseline, err);
+            return false;
+        }
+    };
+
+    let mut ok = true;
+
+    for registry in registries {
+        let rows: Vec<_> = registry
+            .solutions
+            .iter()
+            .filter(|s| args.day.map(|d| d == s.day).unwrap_or(true))
+            .filter(|s| args.part.map(|p| p == s.part).unwrap_or(true))
+            .map(|solution| Row {
+                solution,
+                result: bench_solution(registry, solution, args),
+                baseline: baseline.get(solution, args.demo).copied(),
+            })
+            .collect();
+
+        print!("{}", render(registry.year, &rows, args, &output));
+
+        for row in &rows {
+            match &row.result {
+                Some(Ok(stats)) => baseline.insert(row.solution, args.demo, *stats),
+                Some(Err(_)) => ok = false,
+                None => {}
+            }
+        }
+    }
+
+    if args.save {
+        if let Err(err) = fs::write(&args.baseline, baseline.to_json()) {
+            eprintln!("Could not write baseline {}: {}", args.baseline, err);
+            return false;
+        }
+        println!("Saved {} results to {}", baseline.len(), args.baseline);
+    }
+
+    ok
+}
+
+fn change(stats: &Stats, baseline: &Option<Stats>, output: &Output) -> String {
+    let Some(change) = baseline.and_then(|b| stats.change(&b)) else {
+        return format!("{:6}", "");
+    };
+
+    let text = format!("{:>+5.1}%", change);
+    match change {
+        c if c <= -NOISE => output.paint(State::Passed, &text),
+        c if c >= NOISE => output.paint(State::Failed, &text),
+        _ => text,
+    }
+}
+
+fn render(year: u16, rows: &[Row], args: &BenchArgs, output: &Output) -> String {
+    let mut out = format!(
+        "Advent of Code {} ({} input, {} warmup, {} iterations)\n{:>4} {:>5} {:>8} {:>11} {:>11} {:>11} {:>11}  change\n",
+        year,
+        if args.demo { "demo" } else { "real" },
+        args.warmup,
+        args.iterations.max(1),
+        "day",
+        "part",
+        "version",
+        "min",
+        "median",
+        "mean",
+        "stddev"
+    );
+
+    for row in rows {
+        let stats = match &row.result {
+            Some(Ok(stats)) => format!(
+                "{:>9.3}ms {:>9.3}ms {:>9.3}ms {:>9.3}ms  {}  {}",
+                stats.min_ms,
+                stats.median_ms,
+                stats.mean_ms,
+                stats.stddev_ms,
+                change(stats, &row.baseline, output),
+                stats.memory.map(|m| format::memory(&m)).unwrap_or_default()
+            ),
+            Some(Err(err)) => output.paint(State::Failed, &format!("ERROR {}", err)),
+            None => output.paint(State::Skipped, "skip"),
+        };
+
+        out.push_str(
+            format!(
+                "{:>4} {:>5} {:>8} {}",
+                format!("{:02}", row.solution.day),
+                format!("{:02}", row.solution.part),
+                format!("v{}", row.solution.version),
+                stats
+            )
+            .trim_end(),
+        );
+        out.push('\n');
+    }
+
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::alloc::Memory;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        bench: BenchArgs,
+    }
+
+    #[test]
+    fn bench_table() {
+        let args = Cli::parse_from(["aoc", "--iterations", "10"]).bench;
+        let solutions = [
+            Solution::new(2022, 1, 1, 1, |_| Ok("".to_string())),
+            Solution::new(2022, 1, 2, 1, |_| Ok("".to_string())),
+            Solution::new(2022, 2, 1, 2, |_| Ok("".to_string())),
+        ];
+        let stats = Stats {
+            iterations: 10,
+            min_ms: 1.0,
+            median_ms: 1.5,
+            mean_ms: 1.6,
+            stddev_ms: 0.25,
+            memory: None,
+        };
+        let rows = [
+            Row {
+                solution: &solutions[0],
+                result: Some(Ok(stats)),
+                baseline: Some(Stats {
+                    median_ms: 2.0,
+                    ..stats
+                }),
+            },
+            Row {
+                solution: &solutions[0],
+                result: Some(Ok(Stats {
+                    memory: Some(Memory {
+                        peak_bytes: 4096,
+                        allocated_bytes: 8192,
+                        allocations: 3,
+                    }),
+                    ..stats
+                })),
+                baseline: None,
+            },
+            Row {
+                solution: &solutions[1],
+                result: Some(Err("boom".to_string())),
+                baseline: None,
+            },
+            Row {
+                solution: &solutions[2],
+                result: None,
+                baseline: None,
+            },
+        ];
+
+        let out = render(2022, &rows, &args, &Output::plain());
+        assert_eq!(
+            out,
+            "Advent of Code 2022 (real input, 3 warmup, 10 iterations)\n\
+             \x20day  part  version         min      median        mean      stddev  change\n\
+             \x20 01    01       v1     1.000ms     1.500ms     1.600ms     0.250ms  -25.0%\n\
+             \x20 01    01       v1     1.000ms     1.500ms     1.600ms     0.250ms          peak 4.0 KiB, 8.0 KiB in 3 allocations\n\
+             \x20 01    02       v1 ERROR boom\n\
+             \x20 02    01       v2 skip\n\n"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use aoc_common::output::{ColorChoice, Output, Star};
+use aoc_common::solution::Registry;
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct CalendarArgs {
+    /// Year of the calendar, all years are shown when omitted
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// When to use colors
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+}
+
+pub fn run(registries: &[Registry], args: &CalendarArgs) {
+    let output = Output::new(args.color);
+
+    for registry in registries {
+        println!("{}", render(registry, &output));
+    }
+}
+
+// A part earns its star when there is a registered solution for it and the
+// answer for the real input is known.
+fn stars(registry: &Registry, day: u8) -> usize {
+    (1..=2)
+        .filter(|part| {
+            registry
+                .solutions
+                .iter()
+                .any(|s| s.day == day && s.part == *part)
+                && registry.answers.get(day, *part, false).is_some()
+        })
+        .count()
+}
+
+fn render(registry: &Registry, output: &Output) -> String {
+    let total_stars: usize = (1..=25).map(|day| stars(registry, day)).sum();
+
+    let mut out = format!(
+        "Advent of Code {}  {}/50 stars ({}%)\n\n",
+        registry.year,
+        total_stars,
+        total_stars * 100 / 50
+    );
+
+    for week in 0..5 {
+        let mut line = String::new();
+
+        for day in (week * 5 + 1)..=(week * 5 + 5) {
+            let cell = match stars(registry, day) {
+                2 => output.star(Star::Gold, "**"),
+                1 => output.star(Star::Silver, "* "),
+                _ => output.star(Star::Empty, ".."),
+            };
+
+            line.push_str(&format!("  {:>2} {}", day, cell));
+        }
+
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers::Answers;
+    use aoc_common::solution::Solution;
+
+    const ANSWERS: &str = r#"
+[day01.part01.real]
+answer = "70000"
+input_hash = ""
+
+[day01.part02.real]
+answer = "200158"
+input_hash = ""
+
+[day02.part01.demo]
+answer = "15"
+input_hash = ""
+
+[day02.part01.real]
+answer = "13052"
+input_hash = ""
+
+# without a registered solution
+[day02.part02.real]
+answer = "12"
+input_hash = ""
+"#;
+
+    fn registry() -> Registry {
+        Registry {
+            year: 2022,
+            src_dir: "",
+            solutions: vec![
+                Solution::new(2022, 1, 1, 1, |_| Ok("".to_string())),
+                Solution::new(2022, 1, 2, 1, |_| Ok("".to_string())),
+                Solution::new(2022, 2, 1, 1, |_| Ok("".to_string())),
+                // without a known answer
+                Solution::new(2022, 3, 1, 1, |_| Ok("".to_string())),
+            ],
+            answers: Answers::parse(ANSWERS).unwrap(),
+            generators: vec![],
+        }
+    }
+
+    #[test]
+    fn stars_by_day() {
+        let registry = registry();
+        assert_eq!(stars(&registry, 1), 2);
+        assert_eq!(stars(&registry, 2), 1);
+        assert_eq!(stars(&registry, 3), 0);
+    }
+
+    #[test]
+    fn render_calendar() {
+        let out = render(&registry(), &Output::plain());
+        assert_eq!(
+            out,
+            "Advent of Code 2022  3/50 stars (6%)\n\
+             \n   1 **   2 *    3 ..   4 ..   5 ..\n\
+             \x20  6 ..   7 ..   8 ..   9 ..  10 ..\n\
+             \x20 11 ..  12 ..  13 ..  14 ..  15 ..\n\
+             \x20 16 ..  17 ..  18 ..  19 ..  20 ..\n\
+             \x20 21 ..  22 ..  23 ..  24 ..  25 ..\n"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use aoc_common::solution::Registry;
+use clap::Args;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct GenerateArgs {
+    /// Year of the puzzle
+    #[arg(long)]
+    pub year: u16,
+
+    /// Day of the puzzle
+    #[arg(long)]
+    pub day: u8,
+
+    /// Seed of the random input, the same seed always gives the same input
+    #[arg(long, default_value_t = 1)]
+    pub seed: u64,
+
+    /// Size of the input, the amount of lines, groups or ranges depending on the puzzle
+    #[arg(long, default_value_t = 100)]
+    pub size: usize,
+
+    /// File to write the input to, printed when omitted
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+}
+
+// Writes a random input for a day, returns false if the day has no generator.
+pub fn run(registry: &Registry, args: &GenerateArgs) -> bool {
+    let Some(generator) = registry.generator(args.day) else {
+        eprintln!(
+            "No input generator for year={} day={}",
+            registry.year, args.day
+        );
+        return false;
+    };
+
+    let input = (generator.generate)(args.seed, args.size);
+
+    let result = match &args.output {
+        Some(path) => fs::write(path, input),
+        None => io::stdout().write_all(input.as_bytes()),
+    };
+
+    if let Err(err) = result {
+        eprintln!("Could not write the input: {}", err);
+        return false;
+    }
+
+    true
+}
@@ -0,0 +1,88 @@
+use aoc_common::solution::Registry;
+use clap::{Parser, Subcommand};
+
+mod bench;
+mod calendar;
+mod generate;
+mod report;
+mod snapshot;
+mod test;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Benchmark the solutions and compare them with a saved baseline
+    Bench(bench::BenchArgs),
+    /// Show the calendar of stars of each year
+    Calendar(calendar::CalendarArgs),
+    /// Write a random input for a day puzzle
+    Generate(generate::GenerateArgs),
+    /// Run every solution of a year and write an HTML progress report
+    Report(report::ReportArgs),
+    /// Record the current answers or check them against the last snapshot
+    Snapshot(snapshot::SnapshotArgs),
+    /// Check the solutions against the known answers
+    Test(test::TestArgs),
+}
+
+fn registries() -> Vec<Registry> {
+    vec![aoc_2022_rust::registry(), aoc_2023_rust::registry()]
+}
+
+// Registries of the requested year or all of them when no year is given.
+fn select_years(year: Option<u16>) -> Vec<Registry> {
+    let registries: Vec<_> = registries()
+        .into_iter()
+        .filter(|r| year.map(|y| y == r.year).unwrap_or(true))
+        .collect();
+
+    if registries.is_empty() {
+        eprintln!("No solutions registered for year {:?}", year);
+        std::process::exit(2);
+    }
+
+    registries
+}
+
+fn main() {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Bench(bench_args) => {
+            if !bench::run(&select_years(bench_args.year), &bench_args) {
+                std::process::exit(1);
+            }
+        }
+        Command::Calendar(calendar_args) => {
+            calendar::run(&select_years(calendar_args.year), &calendar_args);
+        }
+        Command::Generate(generate_args) => {
+            for registry in select_years(Some(generate_args.year)) {
+                if !generate::run(&registry, &generate_args) {
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Report(report_args) => {
+            for registry in select_years(report_args.year) {
+                report::run(&registry, &report_args);
+            }
+        }
+        Command::Snapshot(snapshot_args) => {
+            if !snapshot::run(&select_years(snapshot_args.year), &snapshot_args) {
+                std::process::exit(1);
+            }
+        }
+        Command::Test(test_args) => {
+            if !test::run(&select_years(test_args.year), &test_args) {
+                std::process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use aoc_common::output::{ColorChoice, Output, State};
+use aoc_common::runner::{self, Record, Status};
+use aoc_common::solution::{Registry, Solution};
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Args, Debug)]
+pub struct ReportArgs {
+    /// Year of the report, one report per year is written when omitted
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// Report file path (defaults to report-<year>.html)
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+
+    /// Do not include the answers in the report
+    #[arg(long)]
+    pub hide_answers: bool,
+
+    /// Base URL to link the source files (e.g. a repository URL),
+    /// local file links are used when omitted
+    #[arg(long)]
+    pub source_url: Option<String>,
+
+    /// When to use colors
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+}
+
+// A solution with its results for the demo and real inputs.
+struct Row<'a> {
+    solution: &'a Solution,
+    demo: Record,
+    real: Record,
+}
+
+pub fn run(registry: &Registry, args: &ReportArgs) {
+    let output = Output::new(args.color);
+    let mut rows = vec![];
+
+    for solution in &registry.solutions {
+        println!(
+            "{} Running year={} {}",
+            output.icon(State::Running),
+            registry.year,
+            solution.name()
+        );
+
+        rows.push(Row {
+            solution,
+            demo: runner::run_solution(registry, solution, true),
+            real: runner::run_solution(registry, solution, false),
+        });
+    }
+
+    let html = render(registry, &rows, args);
+
+    let path = args
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(format!("report-{}.html", registry.year)));
+
+    match fs::write(&path, html) {
+        Ok(()) => println!(
+            "{} Report written to {}",
+            output.icon(State::Passed),
+            path.display()
+        ),
+        Err(err) => {
+            eprintln!(
+                "{} Could not write report {}: {}",
+                output.icon(State::Failed),
+                path.display(),
+                err
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+// A part earns its star when any of its versions passes with the real input.
+fn stars(rows: &[Row], day: u8) -> usize {
+    (1..=2)
+        .filter(|part| {
+            rows.iter().any(|r| {
+                r.solution.day == day && r.solution.part == *part && r.real.status == Status::Passed
+            })
+        })
+        .count()
+}
+
+fn source_link(registry: &Registry, solution: &Solution, source_url: &Option<String>) -> String {
+    let src_dir = Path::new(registry.src_dir);
+
+    match source_url {
+        Some(base) => {
+            let crate_dir = src_dir
+                .parent()
+                .and_then(|p| p.file_name())
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            format!(
+                "{}/{}/src/{}",
+                base.trim_end_matches('/'),
+                crate_dir,
+                solution.source()
+            )
+        }
+        None => format!("file://{}", src_dir.join(solution.source()).display()),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+// The expected answer is left out of the title of failed records when the
+// answers are hidden.
+fn status_cell(record: &Record, hide_answers: bool) -> String {
+    let title = match record.status {
+        Status::Failed if hide_answers => "".to_string(),
+        Status::Failed => format!(
+            "expected {}",
+            escape(record.expected.as_deref().unwrap_or(""))
+        ),
+        Status::Error => escape(record.error.as_deref().unwrap_or("")),
+        _ => "".to_string(),
+    };
+
+    format!(
+        "<td class=\"{status}\" title=\"{title}\">{status}<small>{time}</small></td>",
+        status = record.status.label(),
+        title = title,
+        time = millis(record.duration),
+    )
+}
+
+const STYLE: &str = "
+body { background: #0f0f23; color: #cccccc; font-family: 'Source Code Pro', monospace; margin: 2em; }
+h1 { color: #00cc00; text-shadow: 0 0 2px #00cc00; }
+a { color: #009900; }
+.calendar { display: grid; grid-template-columns: repeat(5, 7em); gap: 0.5em; margin-bottom: 2em; }
+.day { border: 1px solid #333340; padding: 0.5em; text-align: center; }
+.day .stars { font-size: 1.4em; color: #333340; }
+.day.silver .stars { color: #9999cc; }
+.day.gold .stars { color: #ffff66; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #333340; padding: 0.3em 0.8em; text-align: left; }
+td small { display: block; color: #777788; }
+.passed { color: #00cc00; }
+.failed, .error { color: #ff4444; }
+.unknown { color: #aaaa55; }
+.hidden { color: #555566; }
+";
+
+fn render(registry: &Registry, rows: &[Row], args: &ReportArgs) -> String {
+    let mut html = String::new();
+
+    let total_stars: usize = (1..=25).map(|day| stars(rows, day)).sum();
+    let total_time: Duration = rows.iter().map(|r| r.demo.duration + r.real.duration).sum();
+
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<title>Advent of Code {}</title>\n<style>{}</style>\n</head>\n<body>\n",
+        registry.year, STYLE
+    ));
+    html.push_str(&format!(
+        "<h1>Advent of Code {}</h1>\n<p>{}/50 stars &middot; {} solutions &middot; total time {}</p>\n",
+        registry.year,
+        total_stars,
+        rows.len(),
+        millis(total_time)
+    ));
+
+    // Calendar grid
+    html.push_str("<div class=\"calendar\">\n");
+    for day in 1..=25 {
+        let earned = stars(rows, day);
+        let class = match earned {
+            2 => "gold",
+            1 => "silver",
+            _ => "none",
+        };
+
+        html.push_str(&format!(
+            "<div class=\"day {}\">{}<div class=\"stars\">{}{}</div></div>\n",
+            class,
+            day,
+            "★".repeat(earned),
+            "☆".repeat(2 - earned),
+        ));
+    }
+    html.push_str("</div>\n");
+
+    // Results table
+    html.push_str("<table>\n<tr><th>Day</th><th>Part</th><th>Version</th><th>Answer</th><th>Demo</th><th>Real</th><th>Source</th></tr>\n");
+    for row in rows {
+        let answer = if args.hide_answers {
+            "<span class=\"hidden\">hidden</span>".to_string()
+        } else {
+            escape(row.real.answer.as_deref().unwrap_or(""))
+        };
+
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td>{}{}<td><a href=\"{}\">{}</a></td></tr>\n",
+            row.solution.day,
+            row.solution.part,
+            row.solution.version,
+            answer,
+            status_cell(&row.demo, args.hide_answers),
+            status_cell(&row.real, args.hide_answers),
+            escape(&source_link(registry, row.solution, &args.source_url)),
+            escape(&row.solution.source()),
+        ));
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers::Answers;
+
+    fn record(day: u8, part: u8, demo: bool, status: Status, answer: &str) -> Record {
+        Record {
+            year: 2022,
+            day,
+            part,
+            version: 1,
+            demo,
+            answer: Some(answer.to_string()),
+            expected: None,
+            status,
+            duration: Duration::from_millis(2),
+            parse_duration: None,
+            solve_duration: Duration::from_millis(2),
+            memory: None,
+            budget: Default::default(),
+            over_budget: None,
+            unreliable: false,
+            crash: None,
+            error: None,
+        }
+    }
+
+    fn registry() -> Registry {
+        Registry {
+            year: 2022,
+            src_dir: "/code/aoc_2022_rust/src",
+            solutions: vec![
+                Solution::new(2022, 1, 1, 1, |_| Ok("".to_string())),
+                Solution::new(2022, 1, 2, 1, |_| Ok("".to_string())),
+                Solution::new(2022, 2, 1, 1, |_| Ok("".to_string())),
+            ],
+            answers: Answers::default(),
+            generators: vec![],
+        }
+    }
+
+    fn args(hide_answers: bool) -> ReportArgs {
+        ReportArgs {
+            year: Some(2022),
+            output: None,
+            hide_answers,
+            source_url: Some("https://github.com/me/aoc/blob/main/".to_string()),
+            color: ColorChoice::Never,
+        }
+    }
+
+    fn rows(registry: &Registry) -> Vec<Row<'_>> {
+        let s = &registry.solutions;
+        vec![
+            Row {
+                solution: &s[0],
+                demo: record(1, 1, true, Status::Passed, "24000"),
+                real: record(1, 1, false, Status::Passed, "<70000>"),
+            },
+            Row {
+                solution: &s[1],
+                demo: record(1, 2, true, Status::Passed, "45000"),
+                real: record(1, 2, false, Status::Passed, "200158"),
+            },
+            Row {
+                solution: &s[2],
+                demo: record(2, 1, true, Status::Passed, "15"),
+                real: record(2, 1, false, Status::Unknown, "13052"),
+            },
+        ]
+    }
+
+    #[test]
+    fn stars_by_day() {
+        let registry = registry();
+        let rows = rows(&registry);
+
+        assert_eq!(stars(&rows, 1), 2);
+        assert_eq!(stars(&rows, 2), 0);
+    }
+
+    #[test]
+    fn render_report() {
+        let registry = registry();
+        let html = render(&registry, &rows(&registry), &args(false));
+
+        assert!(html.contains("<h1>Advent of Code 2022</h1>"));
+        assert!(html.contains("2/50 stars"));
+        assert!(html.contains("<div class=\"day gold\">1<div class=\"stars\">★★</div></div>"));
+        assert!(html.contains("<div class=\"day none\">2<div class=\"stars\">☆☆</div></div>"));
+        assert!(html.contains("&lt;70000&gt;"));
+        assert!(html.contains(
+            "<a href=\"https://github.com/me/aoc/blob/main/aoc_2022_rust/src/day01/part02.rs\">"
+        ));
+    }
+
+    #[test]
+    fn render_report_hidden_answers() {
+        let registry = registry();
+        let html = render(&registry, &rows(&registry), &args(true));
+
+        assert!(!html.contains("200158"));
+        assert!(html.contains("<span class=\"hidden\">hidden</span>"));
+    }
+
+    #[test]
+    fn render_report_hides_expected_answers() {
+        let registry = registry();
+        let mut rows = rows(&registry);
+        rows[2].real = Record {
+            expected: Some("13005".to_string()),
+            ..record(2, 1, false, Status::Failed, "13052")
+        };
+
+        let html = render(&registry, &rows, &args(false));
+        assert!(html.contains("title=\"expected 13005\""));
+
+        let html = render(&registry, &rows, &args(true));
+        assert!(!html.contains("13005"));
+        assert!(!html.contains("13052"));
+    }
+}
@@ -0,0 +1,302 @@
+use aoc_common::answers::{self, input_kind, Entry};
+use aoc_common::input;
+use aoc_common::output::{ColorChoice, Output, State};
+use aoc_common::runner::{self, Status};
+use aoc_common::snapshot::{self, Snapshot};
+use aoc_common::solution::{Registry, Solution};
+use clap::Args;
+use std::fs;
+
+#[derive(Args, Debug)]
+pub struct SnapshotArgs {
+    /// Year to snapshot, all years are used when omitted
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// Compare the current answers with the snapshot instead of recording them
+    #[arg(long)]
+    pub check: bool,
+
+    /// Also run the answers marked with `ignore` in answers.toml
+    #[arg(long)]
+    pub include_ignored: bool,
+
+    /// When to use colors
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+}
+
+// Current answer of a solution for an input compared to the snapshot.
+#[derive(Debug, PartialEq)]
+enum Change {
+    Unchanged,
+    Changed { old: String, new: String },
+    // Not in the snapshot yet
+    New(String),
+    // The input is not the one the snapshot was recorded with
+    InputChanged,
+    Error(String),
+    Skipped,
+}
+
+fn current(
+    registry: &Registry,
+    solution: &Solution,
+    demo: bool,
+    include_ignored: bool,
+) -> Option<Result<Entry, String>> {
+    let ignored = registry
+        .answers
+        .get(solution.day, solution.part, demo)
+        .is_some_and(|e| e.ignored(solution.version).is_some());
+
+    if ignored && !include_ignored {
+        return None;
+    }
+
+    let input = match input::load(registry.src_dir, solution.day, solution.part, demo) {
+        Ok(input) => input,
+        Err(err) => return Some(Err(format!("could not read input: {}", err))),
+    };
+
+    let record = runner::run_solution(registry, solution, demo);
+    Some(match (record.status, record.answer) {
+        (Status::Error, _) | (_, None) => Err(record.error.unwrap_or_default()),
+        (_, Some(answer)) => Ok(Entry {
+            answer,
+            input_hash: answers::hash(&input),
+            ignore: None,
+            ignore_versions: None,
+        }),
+    })
+}
+
+fn compare(old: Option<&Entry>, new: Option<Result<Entry, String>>) -> Change {
+    match (old, new) {
+        (_, None) => Change::Skipped,
+        (_, Some(Err(err))) => Change::Error(err),
+        (None, Some(Ok(new))) => Change::New(new.answer),
+        (Some(old), Some(Ok(new))) if old.input_hash != new.input_hash => Change::InputChanged,
+        (Some(old), Some(Ok(new))) if old.answer != new.answer => Change::Changed {
+            old: old.answer.clone(),
+            new: new.answer,
+        },
+        (Some(_), Some(Ok(_))) => Change::Unchanged,
+    }
+}
+
+// Records the current answers of every solution. Entries that can not be
+// run now (ignored or failing) keep their previous value.
+fn record(registry: &Registry, args: &SnapshotArgs, output: &Output) -> bool {
+    let path = snapshot::path(registry);
+    let mut snapshot = match Snapshot::load(&path) {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            eprintln!(
+                "{} Invalid snapshot {}: {}",
+                output.icon(State::Failed),
+                path.display(),
+                err
+            );
+            return false;
+        }
+    };
+
+    let mut ok = true;
+
+    for solution in &registry.solutions {
+        for demo in [true, false] {
+            match current(registry, solution, demo, args.include_ignored) {
+                Some(Ok(entry)) => snapshot.insert(solution, demo, entry),
+                Some(Err(err)) => {
+                    ok = false;
+                    println!(
+                        "{} {} {} {}: {}",
+                        output.icon(State::Failed),
+                        registry.year,
+                        solution.name(),
+                        input_kind(demo),
+                        err
+                    );
+                }
+                None => {}
+            }
+        }
+    }
+
+    match fs::write(&path, snapshot.to_toml()) {
+        Ok(()) => println!(
+            "{} Recorded {} answers to {}",
+            output.icon(State::Passed),
+            snapshot.len(),
+            path.display()
+        ),
+        Err(err) => {
+            eprintln!(
+                "{} Could not write {}: {}",
+                output.icon(State::Failed),
+                path.display(),
+                err
+            );
+            return false;
+        }
+    }
+
+    ok
+}
+
+// Reports every answer that changed since the snapshot was recorded.
+fn check(registry: &Registry, args: &SnapshotArgs, output: &Output) -> bool {
+    let path = snapshot::path(registry);
+    let snapshot = match Snapshot::load(&path) {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            eprintln!(
+                "{} Invalid snapshot {}: {}",
+                output.icon(State::Failed),
+                path.display(),
+                err
+            );
+            return false;
+        }
+    };
+
+    let mut changes = vec![];
+    for solution in &registry.solutions {
+        for demo in [true, false] {
+            let new = current(registry, solution, demo, args.include_ignored);
+            changes.push((solution, demo, compare(snapshot.get(solution, demo), new)));
+        }
+    }
+
+    print!("{}", render(registry.year, &changes, output));
+
+    changes
+        .iter()
+        .all(|(_, _, c)| !matches!(c, Change::Changed { .. } | Change::Error(_)))
+}
+
+fn render(year: u16, changes: &[(&Solution, bool, Change)], output: &Output) -> String {
+    let mut out = format!("Advent of Code {}\n", year);
+
+    for (solution, demo, change) in changes {
+        let (state, message) = match change {
+            Change::Unchanged | Change::Skipped => continue,
+            Change::Changed { old, new } => (State::Failed, format!("changed {} -> {}", old, new)),
+            Change::Error(err) => (State::Failed, format!("error {}", err)),
+            Change::New(answer) => (
+                State::Skipped,
+                format!("not in snapshot, answer {}", answer),
+            ),
+            Change::InputChanged => (
+                State::Skipped,
+                "input changed since the snapshot".to_string(),
+            ),
+        };
+
+        out.push_str(&format!(
+            "{} {} {}: {}\n",
+            output.icon(state),
+            solution.name(),
+            input_kind(*demo),
+            output.paint(state, &message)
+        ));
+    }
+
+    let count = |f: fn(&Change) -> bool| changes.iter().filter(|(_, _, c)| f(c)).count();
+    out.push_str(&format!(
+        "{} unchanged, {} changed, {} errors, {} new, {} input changed, {} skipped\n\n",
+        count(|c| *c == Change::Unchanged),
+        count(|c| matches!(c, Change::Changed { .. })),
+        count(|c| matches!(c, Change::Error(_))),
+        count(|c| matches!(c, Change::New(_))),
+        count(|c| *c == Change::InputChanged),
+        count(|c| *c == Change::Skipped),
+    ));
+
+    out
+}
+
+pub fn run(registries: &[Registry], args: &SnapshotArgs) -> bool {
+    let output = Output::new(args.color);
+    let mut ok = true;
+
+    for registry in registries {
+        ok &= match args.check {
+            true => check(registry, args, &output),
+            false => record(registry, args, &output),
+        };
+    }
+
+    ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(answer: &str, input_hash: &str) -> Entry {
+        Entry {
+            answer: answer.to_string(),
+            input_hash: input_hash.to_string(),
+            ignore: None,
+            ignore_versions: None,
+        }
+    }
+
+    #[test]
+    fn compare_answers() {
+        let old = entry("801", "01");
+
+        assert_eq!(
+            compare(Some(&old), Some(Ok(entry("801", "01")))),
+            Change::Unchanged
+        );
+        assert_eq!(
+            compare(Some(&old), Some(Ok(entry("800", "01")))),
+            Change::Changed {
+                old: "801".to_string(),
+                new: "800".to_string()
+            }
+        );
+        assert_eq!(
+            compare(Some(&old), Some(Ok(entry("800", "02")))),
+            Change::InputChanged
+        );
+        assert_eq!(
+            compare(None, Some(Ok(entry("800", "01")))),
+            Change::New("800".to_string())
+        );
+        assert_eq!(
+            compare(Some(&old), Some(Err("boom".to_string()))),
+            Change::Error("boom".to_string())
+        );
+        assert_eq!(compare(Some(&old), None), Change::Skipped);
+    }
+
+    #[test]
+    fn render_changes() {
+        let v1 = Solution::new(2022, 4, 2, 1, |_| Ok("".to_string()));
+        let v2 = Solution::new(2022, 4, 2, 2, |_| Ok("".to_string()));
+        let changes = vec![
+            (&v1, true, Change::Unchanged),
+            (
+                &v1,
+                false,
+                Change::Changed {
+                    old: "801".to_string(),
+                    new: "800".to_string(),
+                },
+            ),
+            (&v2, false, Change::New("801".to_string())),
+        ];
+
+        assert_eq!(
+            render(2022, &changes, &Output::plain()),
+            "Advent of Code 2022\n\
+             ✗ day04_part02 real: changed 801 -> 800\n\
+             - day04_part02_v2 real: not in snapshot, answer 801\n\
+             1 unchanged, 1 changed, 0 errors, 1 new, 0 input changed, 0 skipped\n\n"
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use aoc_common::output::{ColorChoice, Output, State};
+use aoc_common::runner::{self, Record, Status};
+use aoc_common::solution::{Registry, Solution};
+use clap::Args;
+use std::time::Duration;
+
+#[derive(Args, Debug)]
+pub struct TestArgs {
+    /// Year to test, all years are tested when omitted
+    #[arg(long)]
+    pub year: Option<u16>,
+
+    /// Day to test, all days are tested when omitted
+    #[arg(long)]
+    pub day: Option<u8>,
+
+    /// Also run the answers marked with `ignore` in answers.toml
+    #[arg(long)]
+    pub include_ignored: bool,
+
+    /// When to use colors
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+}
+
+// Outcome of a solution against one input, comparing it with the answers store.
+#[derive(Clone, Debug, PartialEq)]
+enum Cell {
+    Pass(Duration),
+    Fail(Duration),
+    // No answer recorded for the input
+    Missing(Duration),
+    // There is an answer recorded, but for a different input
+    Stale(Duration),
+    Error,
+    // The answer is marked as ignored in the store
+    Skip,
+}
+
+impl Cell {
+    fn label(&self) -> String {
+        match self {
+            Cell::Pass(d) => format!("pass {}", millis(*d)),
+            Cell::Fail(d) => format!("FAIL {}", millis(*d)),
+            Cell::Missing(d) => format!("missing {}", millis(*d)),
+            Cell::Stale(d) => format!("stale {}", millis(*d)),
+            Cell::Error => "ERROR".to_string(),
+            Cell::Skip => "skip".to_string(),
+        }
+    }
+
+    fn state(&self) -> State {
+        match self {
+            Cell::Pass(_) => State::Passed,
+            Cell::Fail(_) | Cell::Error => State::Failed,
+            Cell::Missing(_) | Cell::Stale(_) | Cell::Skip => State::Skipped,
+        }
+    }
+}
+
+// A solution with its results for the demo and real inputs.
+struct Row<'a> {
+    solution: &'a Solution,
+    demo: (Cell, Option<Record>),
+    real: (Cell, Option<Record>),
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn test_input(
+    registry: &Registry,
+    solution: &Solution,
+    demo: bool,
+    include_ignored: bool,
+) -> (Cell, Option<Record>) {
+    let entry = registry.answers.get(solution.day, solution.part, demo);
+
+    if entry.is_some_and(|e| e.ignored(solution.version).is_some()) && !include_ignored {
+        return (Cell::Skip, None);
+    }
+
+    let record = runner::run_solution(registry, solution, demo);
+    let cell = match record.status {
+        Status::Passed => Cell::Pass(record.duration),
+        Status::Failed => Cell::Fail(record.duration),
+        Status::Unknown if entry.is_some() => Cell::Stale(record.duration),
+        Status::Unknown => Cell::Missing(record.duration),
+        Status::Error => Cell::Error,
+    };
+
+    (cell, Some(record))
+}
+
+// Runs the selected solutions printing the matrix of results.
+// Returns false if any answer is wrong or any solution failed.
+pub fn run(registries: &[Registry], args: &TestArgs) -> bool {
+    let output = Output::new(args.color);
+    let mut ok = true;
+
+    for registry in registries {
+        let rows: Vec<_> = registry
+            .solutions
+            .iter()
+            .filter(|s| args.day.map(|d| d == s.day).unwrap_or(true))
+            .map(|solution| Row {
+                solution,
+                demo: test_input(registry, solution, true, args.include_ignored),
+                real: test_input(registry, solution, false, args.include_ignored),
+            })
+            .collect();
+
+        print!("{}", render(registry.year, &rows, &output));
+
+        ok &= rows
+            .iter()
+            .all(|r| r.demo.0.state() != State::Failed && r.real.0.state() != State::Failed);
+    }
+
+    ok
+}
+
+fn render(year: u16, rows: &[Row], output: &Output) -> String {
+    let mut out = format!(
+        "Advent of Code {}\n{:>4} {:>5} {:>8}  {:<18} real\n",
+        year, "day", "part", "version", "demo"
+    );
+
+    let mut failures = vec![];
+
+    for row in rows {
+        let cells: Vec<String> = [&row.demo.0, &row.real.0]
+            .iter()
+            .map(|cell| output.paint(cell.state(), &format!("{:<18}", cell.label())))
+            .collect();
+
+        out.push_str(
+            format!(
+                "{:>4} {:>5} {:>8}  {} {}",
+                format!("{:02}", row.solution.day),
+                format!("{:02}", row.solution.part),
+                format!("v{}", row.solution.version),
+                cells[0],
+                cells[1]
+            )
+            .trim_end(),
+        );
+        out.push('\n');
+
+        for (cell, record) in [&row.demo, &row.real] {
+            let Some(record) = record else {
+                continue;
+            };
+
+            let kind = if record.demo { "demo" } else { "real" };
+            match cell {
+                Cell::Fail(_) => failures.push(format!(
+                    "{} {} {}: expected {}, got {}",
+                    year,
+                    row.solution.name(),
+                    kind,
+                    record.expected.as_deref().unwrap_or(""),
+                    record.answer.as_deref().unwrap_or("")
+                )),
+                Cell::Error => failures.push(format!(
+                    "{} {} {}: {}",
+                    year,
+                    row.solution.name(),
+                    kind,
+                    record.error.as_deref().unwrap_or("")
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    let cells: Vec<&Cell> = rows.iter().flat_map(|r| [&r.demo.0, &r.real.0]).collect();
+    let count = |f: fn(&Cell) -> bool| cells.iter().filter(|c| f(c)).count();
+
+    out.push_str(&format!(
+        "\n{} passed, {} failed, {} missing, {} stale, {} skipped\n",
+        count(|c| matches!(c, Cell::Pass(_))),
+        count(|c| matches!(c, Cell::Fail(_) | Cell::Error)),
+        count(|c| matches!(c, Cell::Missing(_))),
+        count(|c| matches!(c, Cell::Stale(_))),
+        count(|c| matches!(c, Cell::Skip)),
+    ));
+
+    for failure in failures {
+        out.push_str(&format!("{} {}\n", output.icon(State::Failed), failure));
+    }
+
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::answers::{self, Answers, Entry};
+
+    fn registry(src_dir: &'static str) -> Registry {
+        let mut answers = Answers::default();
+        for (part, demo, answer, input, ignore) in [
+            (1, true, "6", "1\n2\n3\n", None),
+            (1, false, "60", "10\n20\n30\n", None),
+            (2, true, "7", "1\n2\n3\n", None),
+            (2, false, "0", "other input", None),
+            (3, true, "6", "1\n2\n3\n", Some("slow".to_string())),
+        ] {
+            answers.insert(
+                1,
+                part,
+                demo,
+                Entry {
+                    answer: answer.to_string(),
+                    input_hash: answers::hash(input),
+                    ignore,
+                    ignore_versions: None,
+                },
+            );
+        }
+
+        let sum = |input: &str| {
+            let total: u32 = input.lines().map(|l| l.parse::<u32>().unwrap()).sum();
+            Ok(total.to_string())
+        };
+
+        Registry {
+            year: 2022,
+            src_dir,
+            solutions: vec![
+                Solution::new(2022, 1, 1, 1, sum),
+                Solution::new(2022, 1, 2, 1, sum),
+                Solution::new(2022, 1, 3, 1, sum),
+                Solution::new(2022, 2, 1, 1, |_| Err("boom".to_string())),
+            ],
+            answers,
+            generators: vec![],
+        }
+    }
+
+    // A directory with the inputs of the registry, unique to the test. The
+    // registry keeps a static path, so the name is leaked.
+    fn src_dir(test: &str) -> &'static str {
+        let dir = std::env::temp_dir().join(format!("aoc_{}_{test}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("day01")).unwrap();
+        std::fs::create_dir_all(dir.join("day02")).unwrap();
+        std::fs::write(dir.join("day01/demo-input.txt"), "1\n2\n3\n").unwrap();
+        std::fs::write(dir.join("day01/input.txt"), "10\n20\n30\n").unwrap();
+        std::fs::write(dir.join("day02/demo-input.txt"), "").unwrap();
+        std::fs::write(dir.join("day02/input.txt"), "").unwrap();
+
+        Box::leak(dir.to_string_lossy().to_string().into_boxed_str())
+    }
+
+    #[test]
+    fn test_matrix() {
+        let dir = src_dir("test_matrix");
+        let registry = registry(dir);
+        let rows: Vec<_> = registry
+            .solutions
+            .iter()
+            .map(|solution| Row {
+                solution,
+                demo: test_input(&registry, solution, true, false),
+                real: test_input(&registry, solution, false, false),
+            })
+            .collect();
+
+        let cells: Vec<_> = rows
+            .iter()
+            .map(|r| {
+                (
+                    std::mem::discriminant(&r.demo.0),
+                    std::mem::discriminant(&r.real.0),
+                )
+            })
+            .collect();
+        let d = |cell: Cell| std::mem::discriminant(&cell);
+
+        assert_eq!(
+            cells,
+            vec![
+                (d(Cell::Pass(Duration::ZERO)), d(Cell::Pass(Duration::ZERO))),
+                (
+                    d(Cell::Fail(Duration::ZERO)),
+                    d(Cell::Stale(Duration::ZERO))
+                ),
+                (d(Cell::Skip), d(Cell::Missing(Duration::ZERO))),
+                (d(Cell::Error), d(Cell::Error)),
+            ]
+        );
+
+        let out = render(2022, &rows, &Output::plain());
+        assert!(out.starts_with("Advent of Code 2022\n day  part  version  demo"));
+        assert!(out.contains("\n  01    01       v1  pass "));
+        assert!(out.contains("2 passed, 3 failed, 1 missing, 1 stale, 1 skipped\n"));
+        assert!(out.contains("✗ 2022 day01_part02 demo: expected 7, got 6\n"));
+        assert!(out.contains("✗ 2022 day02_part01 real: boom\n"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
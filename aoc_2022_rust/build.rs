@@ -0,0 +1,4 @@
+fn main() {
+    // Generates a test for every solution with a known answer in answers.toml
+    aoc_common::answers::write_tests();
+}
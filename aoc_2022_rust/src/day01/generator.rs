@@ -0,0 +1,36 @@
+use aoc_common::random::Rng;
+
+// Calorie groups of `size` Elves (at least 3), each carrying 1 to 10 food
+// items, with the groups separated by a blank line.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    let elves: Vec<String> = (0..size.max(3))
+        .map(|_| {
+            let items = rng.range(1, 10);
+            (0..items)
+                .map(|_| rng.range(1000, 70000).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect();
+
+    elves.join("\n\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01;
+    use std::io::{BufRead, Cursor};
+
+    #[test]
+    fn generate_valid_input() {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_eq!(input.split("\n\n").count(), 50);
+
+        assert!(day01::part01::process(Cursor::new(input.clone()).lines()).is_ok());
+        assert!(day01::part02::process(Cursor::new(input).lines()).is_ok());
+    }
+}
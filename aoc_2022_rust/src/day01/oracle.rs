@@ -0,0 +1,35 @@
+// Brute-force reference solutions, checked against the optimized ones over
+// generated inputs.
+
+// Calories of each Elf, the input must be groups of numbers separated by a
+// single blank line.
+fn elves(input: &str) -> Option<Vec<u64>> {
+    input
+        .trim_end_matches('\n')
+        .split("\n\n")
+        .map(|group| group.lines().map(|l| l.parse::<u64>().ok()).sum())
+        .collect()
+}
+
+pub fn part01(input: &str) -> Option<String> {
+    elves(input)?.into_iter().max().map(|c| c.to_string())
+}
+
+pub fn part02(input: &str) -> Option<String> {
+    let mut elves = elves(input)?;
+    elves.sort();
+    elves.reverse();
+
+    Some(elves.iter().take(3).sum::<u64>().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    #[test]
+    fn solutions_match_oracle() {
+        property::assert_day(&crate::registry(), 1, &[part01, part02], 30);
+    }
+}
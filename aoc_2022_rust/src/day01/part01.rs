@@ -1,40 +1,28 @@
-use std::fs::File;
-use std::io::{self};
+use std::io::{self, BufRead};
+
+use aoc_common::group::Grouping;
 
 use crate::error::SolutionError;
-use crate::icon;
 
-pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(), SolutionError> {
-    let mut elf_most_calories: u8 = 0;
+// Find the Elf carrying the most Calories.
+// How many total Calories is that Elf carrying?
+pub fn process<B: BufRead>(lines: io::Lines<B>) -> std::result::Result<String, SolutionError> {
     let mut total_most_calories: u32 = 0;
 
-    let mut current_elf: u8 = 1;
-    let mut current_total_calories: u32 = 0;
-
-    for line_result in lines {
-        let line = line_result.map_err(SolutionError::GetLineErr)?;
+    // Each elf lists its calories in a paragraph of its own
+    for elf_result in lines.paragraphs() {
+        let elf = elf_result.map_err(SolutionError::GetLineErr)?;
 
-        if line == "" {
-            current_elf += 1;
-            current_total_calories = 0;
-            continue;
+        let mut current_total_calories: u32 = 0;
+        for line in elf {
+            let calories: u32 = line.parse().map_err(SolutionError::ParseLineErr)?;
+            current_total_calories += calories;
         }
 
-        let calories: u32 = line.parse().map_err(SolutionError::ParseLineErr)?;
-        current_total_calories += calories;
-
         if current_total_calories > total_most_calories {
-            elf_most_calories = current_elf;
             total_most_calories = current_total_calories;
         }
     }
 
-    println!(
-        "{} The elf {} is carrying the most calories {}.",
-        icon::CHECK_MARK,
-        elf_most_calories,
-        total_most_calories
-    );
-
-    Ok(())
+    Ok(total_most_calories.to_string())
 }
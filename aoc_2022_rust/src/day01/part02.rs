@@ -1,49 +1,38 @@
-use std::fs::File;
-use std::io::{self};
+use std::io::{self, BufRead};
+
+use aoc_common::debug;
+use aoc_common::group::Grouping;
 
 use crate::error::SolutionError;
-use crate::icon;
 
-pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(), SolutionError> {
+// Find the top three Elves carrying the most Calories.
+// How many Calories are those Elves carrying in total?
+pub fn process<B: BufRead>(lines: io::Lines<B>) -> std::result::Result<String, SolutionError> {
     let total_top_calories = calc_elves_top_calories(lines)?;
 
-    println!(
-        "{} The sum of the calories carried by top three elves is {}.",
-        icon::CHECK_MARK,
-        total_top_calories,
-    );
-
-    Ok(())
+    Ok(total_top_calories.to_string())
 }
 
-fn calc_elves_top_calories(lines: io::Lines<io::BufReader<File>>) -> Result<u32, SolutionError> {
-    let mut current_elf: u8 = 1;
-    let mut current_total_calories: u32 = 0;
-    let mut elves_calories: Vec<(u8, u32)> = vec![];
-
-    for line_result in lines {
-        let line = line_result.map_err(SolutionError::GetLineErr)?;
+fn calc_elves_top_calories<B: BufRead>(lines: io::Lines<B>) -> Result<u32, SolutionError> {
+    let mut elves_calories: Vec<(usize, u32)> = vec![];
 
-        if line == "" {
-            elves_calories.push((current_elf, current_total_calories));
+    for (i, elf_result) in lines.paragraphs().enumerate() {
+        let elf = elf_result.map_err(SolutionError::GetLineErr)?;
 
-            current_elf += 1;
-            current_total_calories = 0;
-            continue;
+        let mut current_total_calories: u32 = 0;
+        for line in elf {
+            let calories: u32 = line.parse().map_err(SolutionError::ParseLineErr)?;
+            current_total_calories += calories;
         }
 
-        let calories: u32 = line.parse().map_err(SolutionError::ParseLineErr)?;
-        current_total_calories += calories;
+        elves_calories.push((i + 1, current_total_calories));
     }
 
-    // record last elf calories
-    elves_calories.push((current_elf, current_total_calories));
-
     // reverse sorting by calories (most calories at the top)
-    elves_calories.sort_by(|a, b| b.1.cmp(&a.1));
+    elves_calories.sort_by_key(|elf| std::cmp::Reverse(elf.1));
 
     let top_3_elves: Vec<_> = elves_calories.iter().take(3).collect();
-    // println!("  Top 3 elves {:?}", top_3_elves);
+    debug!("  Top 3 elves {:?}", top_3_elves);
 
     let total_top_calories: u32 = top_3_elves
         .into_iter()
@@ -71,12 +60,4 @@ mod tests {
         assert_eq!(total, 45000);
         Ok(())
     }
-
-    #[test]
-    fn real_result_ok() -> Result<(), SolutionError> {
-        let lines = input::load(1, false)?;
-        let total = calc_elves_top_calories(lines)?;
-        assert_eq!(total, 200158);
-        Ok(())
-    }
 }
@@ -0,0 +1,35 @@
+use aoc_common::random::Rng;
+
+// Strategy guide with `size` rounds in the format `A X`.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    (0..size.max(1))
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&['A', 'B', 'C']),
+                rng.choose(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day02;
+    use std::io::{BufRead, Cursor};
+
+    #[test]
+    fn generate_valid_input() {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_eq!(input.lines().count(), 50);
+
+        let lines = || Cursor::new(input.clone()).lines();
+        assert!(day02::part01::process(lines()).is_ok());
+        assert!(day02::part01_v2::process(lines()).is_ok());
+        assert!(day02::part02::process(lines()).is_ok());
+    }
+}
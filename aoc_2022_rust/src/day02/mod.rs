@@ -1,3 +1,5 @@
+pub mod generator;
+pub mod oracle;
 pub mod part01;
 pub mod part01_v2;
 pub mod part02;
@@ -0,0 +1,54 @@
+// Brute-force reference solutions, checked against the optimized ones over
+// generated inputs.
+
+// Rounds as (opponent, second column) numbers from 0 to 2.
+fn rounds(input: &str) -> Option<Vec<(u32, u32)>> {
+    input
+        .lines()
+        .map(|line| {
+            let (first, second) = line.split_once(' ')?;
+            let first = ["A", "B", "C"].iter().position(|c| *c == first)?;
+            let second = ["X", "Y", "Z"].iter().position(|c| *c == second)?;
+            Some((first as u32, second as u32))
+        })
+        .collect()
+}
+
+// Every possible play with its outcome: 0 lost, 1 draw and 2 won
+fn outcome(opponent: u32, you: u32) -> u32 {
+    (0..3).find(|o| (opponent + o + 2) % 3 == you).unwrap()
+}
+
+pub fn part01(input: &str) -> Option<String> {
+    let score: u32 = rounds(input)?
+        .into_iter()
+        .map(|(opponent, you)| you + 1 + outcome(opponent, you) * 3)
+        .sum();
+
+    Some(score.to_string())
+}
+
+pub fn part02(input: &str) -> Option<String> {
+    let score: u32 = rounds(input)?
+        .into_iter()
+        .map(|(opponent, wanted)| {
+            let you = (0..3)
+                .find(|you| outcome(opponent, *you) == wanted)
+                .unwrap();
+            you + 1 + wanted * 3
+        })
+        .sum();
+
+    Some(score.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    #[test]
+    fn solutions_match_oracle() {
+        property::assert_day(&crate::registry(), 2, &[part01, part02], 50);
+    }
+}
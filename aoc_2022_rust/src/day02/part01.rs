@@ -1,11 +1,10 @@
-use std::fs::File;
-use std::io::{self};
+use std::io::{self, BufRead};
+
+use aoc_common::trace;
 
 use crate::error::SolutionError;
-use crate::icon;
 
-pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(), SolutionError> {
-    let mut first_user_score: u32 = 0;
+pub fn process<B: BufRead>(lines: io::Lines<B>) -> std::result::Result<String, SolutionError> {
     let mut second_user_score: u32 = 0;
 
     for line_result in lines {
@@ -25,41 +24,33 @@ pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(),
             (GameOption::Rock, GameOption::Rock)
             | (GameOption::Paper, GameOption::Paper)
             | (GameOption::Scissors, GameOption::Scissors) => {
-                first_user_score += DRAW_VALUE;
                 second_user_score += DRAW_VALUE;
-                // println!("DRAW");
+                trace!("DRAW");
             }
             // Handle first users won
             (GameOption::Rock, GameOption::Scissors)
             | (GameOption::Paper, GameOption::Rock)
             | (GameOption::Scissors, GameOption::Paper) => {
-                // println!("LOST"); // second user
-                first_user_score += WON_VALUE;
+                trace!("LOST"); // second user
                 second_user_score += LOST_VALUE;
             }
             // Handle second user won
             (_, _) => {
-                // println!("WON"); // second user
-                first_user_score += LOST_VALUE;
+                trace!("WON"); // second user
                 second_user_score += WON_VALUE;
             }
         }
 
         let (first, second) = options;
-        // println!("first={:?} second={:?}", first, second);
+        trace!("first={:?} second={:?}", first, second);
 
-        let first_value = get_option_value(&first);
         let second_value = get_option_value(&second);
 
-        first_user_score += first_value;
         second_user_score += second_value;
     }
 
-    println!("{} Final result:", icon::CHECK_MARK);
-    println!("  - first user got {} points", first_user_score);
-    println!("  - second user got {} points", second_user_score);
-
-    Ok(())
+    // The answer is the score of the second user (you)
+    Ok(second_user_score.to_string())
 }
 
 #[derive(Debug)]
@@ -70,7 +61,7 @@ enum GameOption {
 }
 
 // TODO: convert to Result
-fn parse_round(round: &Vec<&str>) -> (GameOption, GameOption) {
+fn parse_round(round: &[&str]) -> (GameOption, GameOption) {
     // First column: A for Rock, B for Paper, and C for Scissors.
     let first = match round[0] {
         "A" => GameOption::Rock,
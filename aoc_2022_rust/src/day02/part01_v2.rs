@@ -1,11 +1,10 @@
-use std::fs::File;
-use std::io::{self};
+use std::io::{self, BufRead};
+
+use aoc_common::trace;
 
 use crate::error::SolutionError;
-use crate::icon;
 
-pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(), SolutionError> {
-    let mut first_user_score: u32 = 0;
+pub fn process<B: BufRead>(lines: io::Lines<B>) -> std::result::Result<String, SolutionError> {
     let mut second_user_score: u32 = 0;
 
     for line_result in lines {
@@ -21,42 +20,34 @@ pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(),
         const WON_VALUE: u32 = 6;
 
         if first.choice == second.choice {
-            first_user_score += DRAW_VALUE;
             second_user_score += DRAW_VALUE;
         } else if first.beats == second.choice {
-            // println!("LOST"); // second user
-            first_user_score += WON_VALUE;
+            trace!("LOST"); // second user
             second_user_score += LOST_VALUE;
         } else {
-            // println!("WON"); // second user
-            first_user_score += LOST_VALUE;
+            trace!("WON"); // second user
             second_user_score += WON_VALUE;
         }
 
-        // println!("first={:?} second={:?}", first, second);
+        trace!("first={:?} second={:?}", first, second);
 
-        let first_value = first.points;
         let second_value = second.points;
 
-        first_user_score += first_value;
         second_user_score += second_value;
     }
 
-    println!("{} Final result:", icon::CHECK_MARK);
-    println!("  - first user got {} points", first_user_score);
-    println!("  - second user got {} points", second_user_score);
-
-    Ok(())
+    // The answer is the score of the second user (you)
+    Ok(second_user_score.to_string())
 }
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 enum Choice {
     Rock,
     Paper,
     Scissors,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 struct Play {
     choice: Choice,
     beats: Choice,
@@ -86,7 +77,7 @@ impl Play {
 }
 
 // TODO: convert to Result
-fn parse_round(round: &Vec<&str>) -> (Play, Play) {
+fn parse_round(round: &[&str]) -> (Play, Play) {
     // First column: A for Rock, B for Paper, and C for Scissors.
     let first = match round[0] {
         "A" => Play::new(Choice::Rock),
@@ -0,0 +1,71 @@
+use aoc_common::random::Rng;
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// Rucksacks in groups of three Elves, `size` rucksacks rounded up to a whole
+// group. Both compartments of a rucksack share exactly one item type and the
+// rucksacks of a group share exactly one badge.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut out = String::new();
+
+    for _ in 0..size.max(1).div_ceil(3) {
+        let mut items = ITEMS.to_vec();
+        rng.shuffle(&mut items);
+
+        // Each rucksack of the group gets its own 17 item types, so the badge
+        // is the only item type they have in common.
+        let badge = items[0];
+        for pool in items[1..].chunks(17) {
+            let shared = pool[0];
+            let (first_pool, second_pool) = pool[1..].split_at(8);
+            let half = rng.range(2, 16) as usize;
+
+            let mut first = vec![shared];
+            let mut second = vec![shared];
+            match rng.chance(0.5) {
+                true => first.push(badge),
+                false => second.push(badge),
+            }
+
+            while first.len() < half {
+                first.push(*rng.choose(first_pool));
+            }
+            while second.len() < half {
+                second.push(*rng.choose(second_pool));
+            }
+
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+
+            out.push_str(&String::from_utf8_lossy(&first));
+            out.push_str(&String::from_utf8_lossy(&second));
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day03;
+    use std::io::{BufRead, Cursor};
+
+    #[test]
+    fn generate_valid_input() {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_eq!(input.lines().count(), 51);
+
+        for line in input.lines() {
+            let (first, second) = line.split_at(line.len() / 2);
+            let shared: Vec<_> = first.chars().filter(|c| second.contains(*c)).collect();
+            assert!(!shared.is_empty() && shared.iter().all(|c| *c == shared[0]));
+        }
+
+        assert!(day03::part01::process(Cursor::new(input.clone()).lines()).is_ok());
+        assert!(day03::part02::process(Cursor::new(input).lines()).is_ok());
+    }
+}
@@ -1,2 +1,4 @@
+pub mod generator;
+pub mod oracle;
 pub mod part01;
 pub mod part02;
@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+// Brute-force reference solutions, checked against the optimized ones over
+// generated inputs.
+
+fn priority(item: char) -> u32 {
+    match item {
+        'a'..='z' => item as u32 - 'a' as u32 + 1,
+        _ => item as u32 - 'A' as u32 + 27,
+    }
+}
+
+// The only item type in common, None when there is not exactly one.
+fn common(items: &[&str]) -> Option<char> {
+    if items
+        .iter()
+        .any(|i| i.is_empty() || !i.chars().all(|c| c.is_ascii_alphabetic()))
+    {
+        return None;
+    }
+
+    let mut shared: HashSet<char> = items[0].chars().collect();
+    for i in &items[1..] {
+        shared = shared.intersection(&i.chars().collect()).copied().collect();
+    }
+
+    match shared.len() {
+        1 => shared.into_iter().next(),
+        _ => None,
+    }
+}
+
+pub fn part01(input: &str) -> Option<String> {
+    let mut total = 0;
+
+    for line in input.lines() {
+        if line.len() % 2 != 0 {
+            return None;
+        }
+
+        let (first, second) = line.split_at(line.len() / 2);
+        total += priority(common(&[first, second])?);
+    }
+
+    Some(total.to_string())
+}
+
+pub fn part02(input: &str) -> Option<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    if !lines.len().is_multiple_of(3) {
+        return None;
+    }
+
+    let mut total = 0;
+    for group in lines.chunks(3) {
+        total += priority(common(group)?);
+    }
+
+    Some(total.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    #[test]
+    fn solutions_match_oracle() {
+        property::assert_day(&crate::registry(), 3, &[part01, part02], 30);
+    }
+}
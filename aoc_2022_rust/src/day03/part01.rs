@@ -1,20 +1,18 @@
-use std::fs::File;
-use std::io::{self};
+use std::io::{self, BufRead};
+
+use aoc_common::trace;
 
 use crate::error::SolutionError;
-use crate::icon;
 
 // Find the item type that appears in both compartments of each rucksack.
 // What is the sum of the priorities of those item types?
-pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(), SolutionError> {
+pub fn process<B: BufRead>(lines: io::Lines<B>) -> std::result::Result<String, SolutionError> {
     let total = calc_priorities(lines)?;
 
-    println!("{} Total number of priorities: {}", icon::CHECK_MARK, total);
-
-    Ok(())
+    Ok(total.to_string())
 }
 
-fn calc_priorities(lines: io::Lines<io::BufReader<File>>) -> Result<usize, SolutionError> {
+fn calc_priorities<B: BufRead>(lines: io::Lines<B>) -> Result<usize, SolutionError> {
     let mut total: usize = 0;
 
     for line_result in lines {
@@ -54,7 +52,7 @@ fn get_backpack_priority(line: String) -> Result<usize, SolutionError> {
                     alphatbet_index + 27
                 };
 
-                // println!("Found match char {f} alphatbet_index {alphatbet_index} result {result}",);
+                trace!("Found match char {f} alphatbet_index {alphatbet_index} result {result}");
                 return Ok(result);
             }
         }
@@ -84,12 +82,4 @@ mod tests {
         assert_eq!(total, 157);
         Ok(())
     }
-
-    #[test]
-    fn real_result_ok() -> Result<(), SolutionError> {
-        let lines = input::load(3, false)?;
-        let total = calc_priorities(lines)?;
-        assert_eq!(total, 7674);
-        Ok(())
-    }
 }
@@ -1,42 +1,32 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self};
+use std::io::{self, BufRead};
+
+use aoc_common::group::Grouping;
+use aoc_common::trace;
 
 use crate::error::SolutionError;
-use crate::icon;
 
 // Find the item type that corresponds to the badges of each three-Elf group.
 // What is the sum of the priorities of those item types?
-pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(), SolutionError> {
+pub fn process<B: BufRead>(lines: io::Lines<B>) -> std::result::Result<String, SolutionError> {
     let total = calc_priorities(lines)?;
 
-    println!("{} Total number of priorities: {}", icon::CHECK_MARK, total);
-
-    Ok(())
+    Ok(total.to_string())
 }
 
-fn calc_priorities(lines: io::Lines<io::BufReader<File>>) -> Result<usize, SolutionError> {
+fn calc_priorities<B: BufRead>(lines: io::Lines<B>) -> Result<usize, SolutionError> {
     let mut total: usize = 0;
-    let mut i: usize = 0;
-    let mut group_lines: Vec<String> = vec![];
-
-    for line_result in lines {
-        let line = line_result.map_err(SolutionError::GetLineErr)?;
-
-        group_lines.push(line);
 
-        if (i + 1) % 3 == 0 {
-            total += get_backpack_priority(&group_lines)?;
-            group_lines.clear();
-        }
+    for group_result in lines.chunks(3) {
+        let group_lines = group_result.map_err(SolutionError::InvalidGroupErr)?;
 
-        i += 1;
+        total += get_backpack_priority(&group_lines)?;
     }
 
     Ok(total)
 }
 
-fn get_backpack_priority(lines: &Vec<String>) -> Result<usize, SolutionError> {
+fn get_backpack_priority(lines: &[String]) -> Result<usize, SolutionError> {
     let alphabet: Vec<_> = vec![
         'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r',
         's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
@@ -46,10 +36,10 @@ fn get_backpack_priority(lines: &Vec<String>) -> Result<usize, SolutionError> {
     let items_3: HashMap<char, bool> = lines[2].chars().map(|c| (c, true)).collect();
 
     for char_1 in lines[0].chars() {
-        let found = match (items_2.get(&char_1), items_3.get(&char_1)) {
-            (Some(true), Some(true)) => true,
-            _ => false,
-        };
+        let found = matches!(
+            (items_2.get(&char_1), items_3.get(&char_1)),
+            (Some(true), Some(true))
+        );
 
         if !found {
             // Not found for this char, move to the next one
@@ -68,7 +58,7 @@ fn get_backpack_priority(lines: &Vec<String>) -> Result<usize, SolutionError> {
             alphatbet_index + 27
         };
 
-        // println!("Found match char {char_1} alphatbet_index {alphatbet_index} result {result}",);
+        trace!("Found match char {char_1} alphatbet_index {alphatbet_index} result {result}");
         return Ok(result);
     }
 
@@ -80,6 +70,8 @@ mod tests {
     use super::*;
     use crate::error::SolutionError;
     use crate::input;
+    use aoc_common::group::GroupError;
+    use std::io::Cursor;
 
     #[test]
     fn demo_result_ok() -> Result<(), SolutionError> {
@@ -94,10 +86,15 @@ mod tests {
     }
 
     #[test]
-    fn real_result_ok() -> Result<(), SolutionError> {
-        let lines = input::load(3, false)?;
-        let total = calc_priorities(lines)?;
-        assert_eq!(total, 2805);
-        Ok(())
+    fn partial_group_is_an_error() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n";
+        let result = calc_priorities(Cursor::new(input).lines());
+        assert!(matches!(
+            result,
+            Err(SolutionError::InvalidGroupErr(GroupError::PartialChunk {
+                size: 3,
+                len: 2
+            }))
+        ));
     }
 }
@@ -0,0 +1,33 @@
+use aoc_common::random::Rng;
+
+// List of `size` section assignment pairs in the format `2-4,6-8`.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    let mut section = || {
+        let start = rng.range(1, 99);
+        let end = rng.range(start, 99);
+        format!("{}-{}", start, end)
+    };
+
+    (0..size.max(1))
+        .map(|_| format!("{},{}\n", section(), section()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day04;
+    use std::io::{BufRead, Cursor};
+
+    #[test]
+    fn generate_valid_input() {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_eq!(input.lines().count(), 50);
+
+        assert!(day04::part01::process(Cursor::new(input.clone()).lines()).is_ok());
+        assert!(day04::part02::process(Cursor::new(input).lines()).is_ok());
+    }
+}
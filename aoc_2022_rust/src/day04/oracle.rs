@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+
+// Brute-force reference solutions, checked against the optimized ones over
+// generated inputs. Sections are expanded into sets of section IDs.
+
+fn section(text: &str) -> Option<HashSet<u32>> {
+    let (start, end) = text.split_once('-')?;
+    let (start, end): (u32, u32) = (start.parse().ok()?, end.parse().ok()?);
+
+    // keep the sets small, the generated sections are below 100
+    if start > end || end > 10_000 {
+        return None;
+    }
+
+    Some((start..=end).collect())
+}
+
+fn pairs(input: &str) -> Option<Vec<(HashSet<u32>, HashSet<u32>)>> {
+    input
+        .lines()
+        .map(|line| {
+            let (a, b) = line.split_once(',')?;
+            Some((section(a)?, section(b)?))
+        })
+        .collect()
+}
+
+pub fn part01(input: &str) -> Option<String> {
+    let total = pairs(input)?
+        .iter()
+        .filter(|(a, b)| a.is_subset(b) || b.is_subset(a))
+        .count();
+
+    Some(total.to_string())
+}
+
+pub fn part02(input: &str) -> Option<String> {
+    let total = pairs(input)?
+        .iter()
+        .filter(|(a, b)| !a.is_disjoint(b))
+        .count();
+
+    Some(total.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    #[test]
+    fn solutions_match_oracle() {
+        property::assert_day(&crate::registry(), 4, &[part01, part02], 50);
+    }
+}
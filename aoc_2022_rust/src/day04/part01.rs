@@ -1,19 +1,18 @@
-use std::fs::File;
-use std::io::{self};
+use std::io::{self, BufRead};
+
+use aoc_common::interval::Interval;
+use aoc_common::pattern::FromLine;
 
 use crate::error::SolutionError;
-use crate::icon;
 
 // In how many assignment pairs does one range fully contain the other?
-pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(), SolutionError> {
+pub fn process<B: BufRead>(lines: io::Lines<B>) -> std::result::Result<String, SolutionError> {
     let total = calc_total_overlaps(lines)?;
 
-    println!("{} Total overlaps: {}", icon::CHECK_MARK, total);
-
-    Ok(())
+    Ok(total.to_string())
 }
 
-fn calc_total_overlaps(lines: io::Lines<io::BufReader<File>>) -> Result<usize, SolutionError> {
+fn calc_total_overlaps<B: BufRead>(lines: io::Lines<B>) -> Result<usize, SolutionError> {
     let mut total: usize = 0;
 
     for line_result in lines {
@@ -31,32 +30,36 @@ fn calc_total_overlaps(lines: io::Lines<io::BufReader<File>>) -> Result<usize, S
 // check if there is a full overlapping where one section
 // contains the other.
 fn is_overlapping(line: String) -> Result<bool, SolutionError> {
-    let parts = line
-        .split_once(",")
-        .ok_or(SolutionError::CouldNotSplitByCommaErr)?;
+    let (a, b) = parse_pair(&line)?;
+
+    // Does either a or b contain the other?
+    Ok(a.contains_interval(&b) || b.contains_interval(&a))
+}
 
-    let a = parse_section(parts.0)?;
-    let b = parse_section(parts.1)?;
+// Sections are written with both ends included, e.g. `2-4`
+#[derive(FromLine)]
+#[pattern("{start}-{end}")]
+struct Section {
+    start: i64,
+    end: i64,
+}
 
-    // Is either a or b overlapping each other?
-    Ok((a.0 <= b.0 && a.1 >= b.1) || (b.0 <= a.0 && b.1 >= a.1))
+#[derive(FromLine)]
+#[pattern("{a},{b}")]
+struct Pair {
+    a: Section,
+    b: Section,
 }
 
-fn parse_section(part: &str) -> Result<(u32, u32), SolutionError> {
-    let raw_sec = part
-        .split_once("-")
-        .ok_or(SolutionError::CouldNotSplitByDashErr)?;
-
-    let start = raw_sec
-        .0
-        .parse()
-        .map_err(SolutionError::CouldNotParseSectionStartValueErr)?;
-    let end = raw_sec
-        .1
-        .parse()
-        .map_err(SolutionError::CouldNotParseSectionEndValueErr)?;
-
-    Ok((start, end))
+// The sections of both elves of an assignment pair, e.g. `2-4,6-8`
+pub fn parse_pair(line: &str) -> Result<(Interval, Interval), SolutionError> {
+    let Pair { a, b } = Pair::from_line(line).map_err(SolutionError::InvalidLineErr)?;
+    let interval = |section: Section| {
+        Interval::inclusive(section.start, section.end)
+            .ok_or_else(|| SolutionError::SectionOverflowErr(line.to_string()))
+    };
+
+    Ok((interval(a)?, interval(b)?))
 }
 
 #[cfg(test)]
@@ -64,6 +67,7 @@ mod tests {
     use super::*;
     use crate::error::SolutionError;
     use crate::input;
+    use aoc_common::fuzz;
 
     #[test]
     fn demo_result_ok() -> Result<(), SolutionError> {
@@ -78,10 +82,19 @@ mod tests {
     }
 
     #[test]
-    fn real_result_ok() -> Result<(), SolutionError> {
-        let lines = input::load(4, false)?;
-        let total = calc_total_overlaps(lines)?;
-        assert_eq!(total, 444);
-        Ok(())
+    fn section_past_the_end_is_an_error() {
+        assert!(matches!(
+            parse_pair("1-9223372036854775807,1-2"),
+            Err(SolutionError::SectionOverflowErr(_))
+        ));
+    }
+
+    #[test]
+    fn fuzz_parse_pair() {
+        fuzz::assert_target(env!("CARGO_MANIFEST_DIR"), "day04_parse_pair", |input| {
+            for line in input.lines() {
+                let _ = parse_pair(line);
+            }
+        });
     }
 }
@@ -1,33 +1,24 @@
-use std::fs::File;
-use std::io::{self};
+use std::io::{self, BufRead};
 
+use super::part01::parse_pair;
 use crate::error::SolutionError;
-use crate::icon;
 
 // In how many assignment pairs does one range fully contain the other?
-pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(), SolutionError> {
+pub fn process<B: BufRead>(lines: io::Lines<B>) -> std::result::Result<String, SolutionError> {
     let total = calc_total_overlaps(lines)?;
 
-    println!("{} Total overlaps: {}", icon::CHECK_MARK, total);
-
-    Ok(())
+    Ok(total.to_string())
 }
 
-fn calc_total_overlaps(lines: io::Lines<io::BufReader<File>>) -> Result<usize, SolutionError> {
+fn calc_total_overlaps<B: BufRead>(lines: io::Lines<B>) -> Result<usize, SolutionError> {
     let mut total: usize = 0;
 
     for line_result in lines {
         let line = line_result.map_err(SolutionError::GetLineErr)?;
 
-        let parts = line
-            .split_once(",")
-            .ok_or(SolutionError::CouldNotSplitByCommaErr)?;
-
-        let a = parse_section(parts.0)?;
-        let b = parse_section(parts.1)?;
+        let (a, b) = parse_pair(&line)?;
 
-        let ok = is_overlapping(&a, &b);
-        if ok {
+        if a.overlaps(&b) {
             total += 1;
         }
     }
@@ -35,65 +26,12 @@ fn calc_total_overlaps(lines: io::Lines<io::BufReader<File>>) -> Result<usize, S
     Ok(total)
 }
 
-#[derive(Debug)]
-struct Section {
-    start: u32,
-    end: u32,
-}
-
-// check if there is any overlapping between two sections
-fn is_overlapping(a: &Section, b: &Section) -> bool {
-    // a and b have either the same start or end values
-    if a.start == b.start || a.end == b.end || a.start == b.end || b.start == a.end {
-        return true;
-    }
-
-    // b within a
-    if a.start <= b.start && a.end >= b.end {
-        return true;
-    }
-
-    // a within b
-    if b.start <= a.start && b.end >= a.end {
-        return true;
-    }
-
-    // overlapping at the beginning
-    if a.start <= b.start && a.end <= b.end && a.end >= b.start {
-        return true;
-    }
-
-    // overlapping at the ending
-    if b.start <= a.start && b.end <= a.end && b.end >= a.start {
-        return true;
-    }
-
-    // no overlapping
-    false
-}
-
-fn parse_section(part: &str) -> Result<Section, SolutionError> {
-    let raw_sec = part
-        .split_once("-")
-        .ok_or(SolutionError::CouldNotSplitByDashErr)?;
-
-    let start = raw_sec
-        .0
-        .parse()
-        .map_err(SolutionError::CouldNotParseSectionStartValueErr)?;
-    let end = raw_sec
-        .1
-        .parse()
-        .map_err(SolutionError::CouldNotParseSectionEndValueErr)?;
-
-    Ok(Section { start, end })
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::error::SolutionError;
     use crate::input;
+    use aoc_common::interval::Interval;
 
     #[test]
     fn demo_result_ok() -> Result<(), SolutionError> {
@@ -109,12 +47,8 @@ mod tests {
         Ok(())
     }
 
-    #[test]
-    fn real_result_ok() -> Result<(), SolutionError> {
-        let lines = input::load(4, false)?;
-        let total = calc_total_overlaps(lines)?;
-        assert_eq!(total, 801);
-        Ok(())
+    fn section(first: i64, last: i64) -> Interval {
+        Interval::inclusive(first, last).unwrap()
     }
 
     #[test]
@@ -122,44 +56,32 @@ mod tests {
         // all tests swipe a and b to test both sections in different position
         let test_cases = vec![
             // equal
-            (Section { start: 1, end: 1 }, Section { start: 1, end: 1 }),
+            (section(1, 1), section(1, 1)),
             // one section start is equal to the other section end
-            (Section { start: 4, end: 5 }, Section { start: 2, end: 4 }),
+            (section(4, 5), section(2, 4)),
             // one section start is equal to the other section start
-            (Section { start: 1, end: 2 }, Section { start: 1, end: 3 }),
+            (section(1, 2), section(1, 3)),
             // one section end is equal to the other section end
-            (Section { start: 2, end: 4 }, Section { start: 3, end: 4 }),
+            (section(2, 4), section(3, 4)),
             // one section is within the other
-            (Section { start: 2, end: 8 }, Section { start: 3, end: 7 }),
+            (section(2, 8), section(3, 7)),
             // demo input expected to be valid
-            (Section { start: 5, end: 7 }, Section { start: 7, end: 9 }),
-            (Section { start: 2, end: 8 }, Section { start: 3, end: 7 }),
-            (Section { start: 6, end: 6 }, Section { start: 4, end: 6 }),
-            (Section { start: 2, end: 6 }, Section { start: 4, end: 8 }),
+            (section(5, 7), section(7, 9)),
+            (section(2, 8), section(3, 7)),
+            (section(6, 6), section(4, 6)),
+            (section(2, 6), section(4, 8)),
             // a few more cases
-            (Section { start: 4, end: 6 }, Section { start: 5, end: 7 }),
-            (Section { start: 4, end: 6 }, Section { start: 5, end: 6 }),
-            (Section { start: 4, end: 6 }, Section { start: 5, end: 5 }),
-            (Section { start: 4, end: 5 }, Section { start: 5, end: 5 }),
-            (Section { start: 3, end: 8 }, Section { start: 2, end: 5 }),
+            (section(4, 6), section(5, 7)),
+            (section(4, 6), section(5, 6)),
+            (section(4, 6), section(5, 5)),
+            (section(4, 5), section(5, 5)),
+            (section(3, 8), section(2, 5)),
         ];
 
         for tc in test_cases {
-            assert_eq!(
-                is_overlapping(&tc.0, &tc.1),
-                true,
-                "a={:?} b={:?}",
-                tc.0,
-                tc.1
-            );
-
-            assert_eq!(
-                is_overlapping(&tc.1, &tc.0),
-                true,
-                "a={:?} b={:?}",
-                tc.1,
-                tc.0
-            );
+            assert!(tc.0.overlaps(&tc.1), "a={:?} b={:?}", tc.0, tc.1);
+
+            assert!(tc.1.overlaps(&tc.0), "a={:?} b={:?}", tc.1, tc.0);
         }
     }
 
@@ -168,27 +90,15 @@ mod tests {
         // all tests swipe a and b to test both sections in different position
         let test_cases = vec![
             // equal
-            (Section { start: 0, end: 0 }, Section { start: 1, end: 1 }),
-            (Section { start: 1, end: 1 }, Section { start: 2, end: 2 }),
-            (Section { start: 1, end: 2 }, Section { start: 3, end: 4 }),
+            (section(0, 0), section(1, 1)),
+            (section(1, 1), section(2, 2)),
+            (section(1, 2), section(3, 4)),
         ];
 
         for tc in test_cases {
-            assert_eq!(
-                is_overlapping(&tc.0, &tc.1),
-                false,
-                "a={:?} b={:?}",
-                tc.0,
-                tc.1
-            );
-
-            assert_eq!(
-                is_overlapping(&tc.1, &tc.0),
-                false,
-                "a={:?} b={:?}",
-                tc.1,
-                tc.0
-            );
+            assert!(!tc.0.overlaps(&tc.1), "a={:?} b={:?}", tc.0, tc.1);
+
+            assert!(!tc.1.overlaps(&tc.0), "a={:?} b={:?}", tc.1, tc.0);
         }
     }
 }
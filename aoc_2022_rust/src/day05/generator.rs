@@ -0,0 +1,78 @@
+use aoc_common::random::Rng;
+
+// Drawing of 3 to 9 crate stacks followed by `size` move instructions. The
+// moves never empty a stack, so every stack has a crate on top at the end.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    let stacks_len = rng.range(3, 9) as usize;
+    let mut stacks: Vec<Vec<char>> = (0..stacks_len)
+        .map(|i| {
+            // the first stack always has a crate to move
+            let min = if i == 0 { 2 } else { 1 };
+            (0..rng.range(min, 8))
+                .map(|_| (b'A' + rng.range(0, 25) as u8) as char)
+                .collect()
+        })
+        .collect();
+
+    let mut out = String::new();
+
+    let height = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|s| match s.get(level) {
+                Some(c) => format!("[{}]", c),
+                None => "   ".to_string(),
+            })
+            .collect();
+
+        out.push_str(row.join(" ").trim_end());
+        out.push('\n');
+    }
+
+    let numbers: Vec<String> = (1..=stacks_len).map(|n| format!(" {} ", n)).collect();
+    out.push_str(numbers.join(" ").trim_end());
+    out.push_str("\n\n");
+
+    for _ in 0..size {
+        let sources: Vec<usize> = (0..stacks_len).filter(|i| stacks[*i].len() > 1).collect();
+        let src = *rng.choose(&sources);
+        let mut dst = rng.index(stacks_len - 1);
+        if dst >= src {
+            dst += 1;
+        }
+
+        let amount = rng.range(1, stacks[src].len() as u64 - 1) as usize;
+        for _ in 0..amount {
+            let c = stacks[src].pop().unwrap();
+            stacks[dst].push(c);
+        }
+
+        out.push_str(&format!(
+            "move {} from {} to {}\n",
+            amount,
+            src + 1,
+            dst + 1
+        ));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day05;
+    use std::io::{BufRead, Cursor};
+
+    #[test]
+    fn generate_valid_input() {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_eq!(input.lines().filter(|l| l.starts_with("move")).count(), 50);
+
+        assert!(day05::part01::process(Cursor::new(input).lines()).is_ok());
+    }
+}
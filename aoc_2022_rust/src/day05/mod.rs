@@ -1 +1,3 @@
+pub mod generator;
+pub mod oracle;
 pub mod part01;
@@ -0,0 +1,82 @@
+// Brute-force reference solution, checked against the optimized one over
+// generated inputs. Crates are moved one at a time on plain vectors.
+
+// Crate of a stack column in a drawing row, None when the row is malformed.
+fn cell(row: &str, col: usize) -> Option<Option<char>> {
+    let chars: Vec<char> = row.chars().skip(col * 4).take(3).collect();
+
+    match chars.as_slice() {
+        [] | [' '] | [' ', ' '] | [' ', ' ', ' '] => Some(None),
+        ['[', c, ']'] if c.is_ascii_uppercase() => Some(Some(*c)),
+        _ => None,
+    }
+}
+
+fn stacks(drawing: &str) -> Option<Vec<Vec<char>>> {
+    let mut rows: Vec<&str> = drawing.lines().collect();
+
+    let numbers: Vec<&str> = rows.pop()?.split_whitespace().collect();
+    let len = numbers.len();
+    if len == 0 || (1..=len).any(|n| numbers[n - 1] != n.to_string()) {
+        return None;
+    }
+
+    let mut stacks = vec![vec![]; len];
+    for (level, row) in rows.iter().rev().enumerate() {
+        if row.len() > len * 4 - 1 {
+            return None;
+        }
+
+        for (col, stack) in stacks.iter_mut().enumerate() {
+            if let Some(c) = cell(row, col)? {
+                // crates can not float over an empty spot
+                if stack.len() != level {
+                    return None;
+                }
+                stack.push(c);
+            }
+        }
+    }
+
+    match stacks.iter().all(|s| !s.is_empty()) {
+        true => Some(stacks),
+        false => None,
+    }
+}
+
+pub fn part01(input: &str) -> Option<String> {
+    let (drawing, moves) = input.split_once("\n\n")?;
+    let mut stacks = stacks(drawing)?;
+
+    for line in moves.lines() {
+        let words: Vec<&str> = line.split(' ').collect();
+        let ["move", amount, "from", src, "to", dst] = words.as_slice() else {
+            return None;
+        };
+
+        let amount: usize = amount.parse().ok()?;
+        let src = src.parse::<usize>().ok()?.checked_sub(1)?;
+        let dst = dst.parse::<usize>().ok()?.checked_sub(1)?;
+        if amount == 0 || src >= stacks.len() || dst >= stacks.len() {
+            return None;
+        }
+
+        for _ in 0..amount {
+            let c = stacks[src].pop()?;
+            stacks[dst].push(c);
+        }
+    }
+
+    stacks.iter().map(|s| s.last().copied()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    #[test]
+    fn solutions_match_oracle() {
+        property::assert_day(&crate::registry(), 5, &[part01], 30);
+    }
+}
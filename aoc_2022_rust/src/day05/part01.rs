@@ -1,40 +1,30 @@
 use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{self};
+use std::io::{self, BufRead};
+
+use aoc_common::pattern::FromLine;
+use aoc_common::{debug, trace};
 
 use crate::error::SolutionError;
-use crate::icon;
 
-#[derive(Debug)]
-struct MoveInstruction {
+#[derive(Debug, FromLine)]
+#[pattern("move {amount} from {src} to {dst}")]
+pub struct MoveInstruction {
     src: usize,
     dst: usize,
     amount: usize,
 }
 
 // After the rearrangement procedure completes, what crate ends up on top of each stack?
-pub fn process(lines: io::Lines<io::BufReader<File>>) -> std::result::Result<(), SolutionError> {
-    let top_stacks = resolve_top_stacks(lines)?;
-
-    println!(
-        "{} Top stacks after rearrangement: {}",
-        icon::CHECK_MARK,
-        top_stacks
-    );
-
-    Ok(())
+pub fn process<B: BufRead>(lines: io::Lines<B>) -> std::result::Result<String, SolutionError> {
+    resolve_top_stacks(load_data(lines)?)
 }
 
-fn resolve_top_stacks(
-    lines: io::Lines<io::BufReader<File>>,
-) -> std::result::Result<String, SolutionError> {
-    let data = load_data(lines)?;
-
+pub fn resolve_top_stacks(data: Procedure) -> std::result::Result<String, SolutionError> {
     let mut stacks = data.0;
     let instructions = data.1;
 
-    // println!("stacks {:?}", stacks);
-    // println!("instructions {:?}", instructions);
+    debug!("stacks {:?}", stacks);
+    debug!("instructions {:?}", instructions);
     rearrenge_stacks(&mut stacks, &instructions)?;
 
     let top_stacks = get_top_stacks(&stacks);
@@ -42,16 +32,17 @@ fn resolve_top_stacks(
     Ok(top_stacks)
 }
 
-fn load_data(
-    lines: io::Lines<io::BufReader<File>>,
-) -> Result<(Vec<VecDeque<char>>, Vec<MoveInstruction>), SolutionError> {
+// Starting stacks and the rearrangement instructions
+pub type Procedure = (Vec<VecDeque<char>>, Vec<MoveInstruction>);
+
+pub fn load_data<B: BufRead>(lines: io::Lines<B>) -> Result<Procedure, SolutionError> {
     let mut stacks: Vec<VecDeque<char>> = vec![];
     let mut instructions: Vec<MoveInstruction> = vec![];
 
     for line_result in lines {
         let line = line_result.map_err(SolutionError::GetLineErr)?;
         if line.starts_with("move") {
-            instructions.push(parse_instruction(line));
+            instructions.push(parse_instruction(line)?);
         } else if line.contains("[") {
             load_stacks(&mut stacks, line);
         }
@@ -61,12 +52,12 @@ fn load_data(
 }
 
 fn rearrenge_stacks(
-    stacks: &mut Vec<VecDeque<char>>,
-    instructions: &Vec<MoveInstruction>,
+    stacks: &mut [VecDeque<char>],
+    instructions: &[MoveInstruction],
 ) -> Result<(), SolutionError> {
     for inst in instructions {
         for _ in 1..=inst.amount {
-            // println!("Move {} from {} to {}", inst.amount, inst.src, inst.dst);
+            trace!("Move {} from {} to {}", inst.amount, inst.src, inst.dst);
             let c = stacks[inst.src - 1].pop_back();
             stacks[inst.dst - 1].push_back(c.unwrap());
         }
@@ -74,7 +65,7 @@ fn rearrenge_stacks(
     Ok(())
 }
 
-fn get_top_stacks(stacks: &Vec<VecDeque<char>>) -> String {
+fn get_top_stacks(stacks: &[VecDeque<char>]) -> String {
     let tops = stacks.iter().fold("".to_string(), |acc, stack| {
         acc + &stack.back().unwrap().to_string()
     });
@@ -117,22 +108,8 @@ fn load_stacks(stacks: &mut Vec<VecDeque<char>>, line: String) {
 // move 3 from 1 to 3
 // move 2 from 2 to 1
 // move 1 from 1 to 2
-fn parse_instruction(line: String) -> MoveInstruction {
-    let parts: Vec<usize> = line
-        .split(" ")
-        .map(|part| part.parse::<usize>().unwrap_or(0))
-        .filter(|n| n.gt(&0))
-        .collect();
-
-    if parts.len() != 3 {
-        panic!("wrong instruction format")
-    }
-
-    MoveInstruction {
-        amount: parts[0],
-        src: parts[1],
-        dst: parts[2],
-    }
+fn parse_instruction(line: String) -> Result<MoveInstruction, SolutionError> {
+    MoveInstruction::from_line(&line).map_err(SolutionError::InvalidLineErr)
 }
 
 #[cfg(test)]
@@ -140,6 +117,7 @@ mod tests {
     use super::*;
     use crate::error::SolutionError;
     use crate::input;
+    use aoc_common::fuzz;
 
     #[test]
     fn demo_result_ok() -> Result<(), SolutionError> {
@@ -150,19 +128,11 @@ mod tests {
         // and Z in stack 3,
         // so you should combine these together and give the Elves the message CMZ.
         let lines = input::load(5, true)?;
-        let top_stacks = resolve_top_stacks(lines)?;
+        let top_stacks = resolve_top_stacks(load_data(lines)?)?;
         assert_eq!(top_stacks, "CMZ");
         Ok(())
     }
 
-    #[test]
-    fn real_result_ok() -> Result<(), SolutionError> {
-        let lines = input::load(5, false)?;
-        let top_stacks = resolve_top_stacks(lines)?;
-        assert_eq!(top_stacks, "SBPQRSCDF");
-        Ok(())
-    }
-
     #[test]
     fn test_load_stacks() {
         // Test loading this stack:
@@ -204,31 +174,63 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_instruction() {
+    fn test_parse_instruction() -> Result<(), SolutionError> {
         // Test loading this instructions:
         // move 1 from 2 to 1
         // move 3 from 1 to 3
         // move 2 from 2 to 1
         // move 1 from 1 to 2
-        let instruction = parse_instruction("move 1 from 2 to 1".to_string());
+        let instruction = parse_instruction("move 1 from 2 to 1".to_string())?;
         assert_eq!(instruction.amount, 1);
         assert_eq!(instruction.src, 2);
         assert_eq!(instruction.dst, 1);
 
-        let instruction = parse_instruction("move 3 from 1 to 3".to_string());
+        let instruction = parse_instruction("move 3 from 1 to 3".to_string())?;
         assert_eq!(instruction.amount, 3);
         assert_eq!(instruction.src, 1);
         assert_eq!(instruction.dst, 3);
 
-        let instruction = parse_instruction("move 2 from 2 to 1".to_string());
+        let instruction = parse_instruction("move 2 from 2 to 1".to_string())?;
         assert_eq!(instruction.amount, 2);
         assert_eq!(instruction.src, 2);
         assert_eq!(instruction.dst, 1);
 
-        let instruction = parse_instruction("move 1 from 1 to 2".to_string());
+        let instruction = parse_instruction("move 1 from 1 to 2".to_string())?;
         assert_eq!(instruction.amount, 1);
         assert_eq!(instruction.src, 1);
         assert_eq!(instruction.dst, 2);
+
+        assert!(matches!(
+            parse_instruction("move 1 from 2".to_string()),
+            Err(SolutionError::InvalidLineErr(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn fuzz_load_stacks() {
+        fuzz::assert_target(env!("CARGO_MANIFEST_DIR"), "day05_load_stacks", |input| {
+            let mut stacks = vec![];
+            for line in input.lines() {
+                load_stacks(&mut stacks, line.to_string());
+            }
+
+            let crates: usize = stacks.iter().map(|s| s.len()).sum();
+            assert!(stacks.len() <= input.len() && crates <= input.len());
+        });
+    }
+
+    #[test]
+    fn fuzz_parse_instruction() {
+        fuzz::assert_target(
+            env!("CARGO_MANIFEST_DIR"),
+            "day05_parse_instruction",
+            |input| {
+                for line in input.lines() {
+                    let _ = parse_instruction(line.to_string());
+                }
+            },
+        );
     }
 
     #[test]
@@ -1,5 +1,8 @@
 use std::num::ParseIntError;
 
+use aoc_common::group::GroupError;
+use aoc_common::pattern::PatternError;
+
 #[derive(Debug)]
 pub enum SolutionError {
     ReadFileErr(std::io::Error),
@@ -7,8 +10,7 @@ pub enum SolutionError {
     ParseLineErr(ParseIntError),
     CharNotFoundErr(char),
     CouldNotFindPriorityErr,
-    CouldNotSplitByCommaErr,
-    CouldNotSplitByDashErr,
-    CouldNotParseSectionStartValueErr(ParseIntError),
-    CouldNotParseSectionEndValueErr(ParseIntError),
+    InvalidLineErr(PatternError),
+    InvalidGroupErr(GroupError<std::io::Error>),
+    SectionOverflowErr(String),
 }
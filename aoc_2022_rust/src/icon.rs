@@ -1,2 +0,0 @@
-pub const BULLET: &str = "\x1b[0;93m•\x1b[0;00m";
-pub const CHECK_MARK: &str = "\x1b[0;32m✓\x1b[0;00m";
@@ -2,6 +2,8 @@ use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
 
+use aoc_common::debug;
+
 use crate::error::SolutionError;
 
 pub fn load(day: u8, demo: bool) -> Result<io::Lines<io::BufReader<File>>, SolutionError> {
@@ -17,7 +19,9 @@ pub fn load(day: u8, demo: bool) -> Result<io::Lines<io::BufReader<File>>, Solut
         false => format!("src/day{}/input.txt", day_label),
     };
 
-    let lines = read_lines(file_name).map_err(SolutionError::ReadFileErr)?;
+    let file_path = Path::new(env!("CARGO_MANIFEST_DIR")).join(file_name);
+
+    let lines = read_lines(file_path).map_err(SolutionError::ReadFileErr)?;
 
     Ok(lines)
 }
@@ -28,7 +32,7 @@ fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where
     P: AsRef<Path>,
 {
-    // println!("Reading file {:?}", filename);
+    debug!("Reading file {:?}", filename.as_ref());
     let file = File::open(filename)?;
     Ok(io::BufReader::new(file).lines())
 }
@@ -0,0 +1,113 @@
+use aoc_common::answers::Answers;
+use aoc_common::solution::{Generator, Registry, Solution};
+use std::io::{self, BufRead};
+
+// project mods
+pub mod error;
+pub mod input;
+
+// days mods
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+
+use error::SolutionError;
+
+pub const YEAR: u16 = 2022;
+
+type Process = fn(io::Lines<io::Cursor<String>>) -> Result<String, SolutionError>;
+
+// Wraps a streaming solution so it can be given the input as text.
+fn solution(day: u8, part: u8, version: u8, process: Process) -> Solution {
+    Solution::new(YEAR, day, part, version, move |input| {
+        let lines = io::Cursor::new(input.to_string()).lines();
+        process(lines).map_err(|err| format!("{:?}", err))
+    })
+}
+
+type Parse<T> = fn(io::Lines<io::Cursor<String>>) -> Result<T, SolutionError>;
+
+// Wraps a solution with its parse step, timed apart from solving.
+fn parsed_solution<T: 'static>(
+    day: u8,
+    part: u8,
+    version: u8,
+    parse: Parse<T>,
+    solve: fn(T) -> Result<String, SolutionError>,
+) -> Solution {
+    Solution::with_parse(
+        YEAR,
+        day,
+        part,
+        version,
+        move |input| {
+            let lines = io::Cursor::new(input.to_string()).lines();
+            parse(lines).map_err(|err| format!("{:?}", err))
+        },
+        move |parsed| solve(parsed).map_err(|err| format!("{:?}", err)),
+    )
+}
+
+pub fn registry() -> Registry {
+    let solutions = vec![
+        // Day 1
+        solution(1, 1, 1, day01::part01::process),
+        solution(1, 2, 1, day01::part02::process),
+        // Day 2
+        solution(2, 1, 1, day02::part01::process),
+        solution(2, 1, 2, day02::part01_v2::process),
+        solution(2, 2, 1, day02::part02::process),
+        // Day 3
+        solution(3, 1, 1, day03::part01::process),
+        solution(3, 2, 1, day03::part02::process),
+        // Day 4
+        solution(4, 1, 1, day04::part01::process),
+        solution(4, 2, 1, day04::part02::process),
+        // Day 5
+        parsed_solution(
+            5,
+            1,
+            1,
+            day05::part01::load_data,
+            day05::part01::resolve_top_stacks,
+        ),
+    ];
+
+    let generators = vec![
+        Generator {
+            day: 1,
+            generate: day01::generator::generate,
+        },
+        Generator {
+            day: 2,
+            generate: day02::generator::generate,
+        },
+        Generator {
+            day: 3,
+            generate: day03::generator::generate,
+        },
+        Generator {
+            day: 4,
+            generate: day04::generator::generate,
+        },
+        Generator {
+            day: 5,
+            generate: day05::generator::generate,
+        },
+    ];
+
+    Registry {
+        year: YEAR,
+        src_dir: concat!(env!("CARGO_MANIFEST_DIR"), "/src"),
+        solutions,
+        answers: Answers::parse(include_str!("../answers.toml")).expect("Invalid answers.toml"),
+        generators,
+    }
+}
+
+#[cfg(test)]
+mod answer_tests {
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}
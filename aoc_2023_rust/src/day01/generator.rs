@@ -0,0 +1,48 @@
+use aoc_common::random::Rng;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// Calibration document with `size` lines mixing letters, digits and spelled
+// out digits. Every line has at least one digit, as both parts need it.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut out = String::new();
+
+    for _ in 0..size.max(1) {
+        let mut tokens: Vec<String> = (0..rng.range(1, 6))
+            .map(|_| match rng.range(0, 2) {
+                0 => (0..rng.range(1, 4))
+                    .map(|_| (b'a' + rng.range(0, 25) as u8) as char)
+                    .collect(),
+                1 => rng.range(1, 9).to_string(),
+                _ => rng.choose(&WORDS).to_string(),
+            })
+            .collect();
+
+        let at = rng.index(tokens.len() + 1);
+        tokens.insert(at, rng.range(1, 9).to_string());
+
+        out.push_str(&tokens.concat());
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_eq!(input.lines().count(), 50);
+
+        day01::part01::resolve_total_calibrations(&input);
+        day01::part02::resolve_total_calibrations(&input);
+    }
+}
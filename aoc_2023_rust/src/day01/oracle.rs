@@ -0,0 +1,53 @@
+// Brute-force reference solutions, checked against the optimized ones over
+// generated inputs. Every position of a line is tried as the start of a digit.
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn calibration(digits: Vec<u32>) -> Option<u32> {
+    Some(digits.first()? * 10 + digits.last()?)
+}
+
+pub fn part01(input: &str) -> Option<String> {
+    let mut total = 0;
+
+    for line in input.lines() {
+        total += calibration(line.chars().filter_map(|c| c.to_digit(10)).collect())?;
+    }
+
+    Some(total.to_string())
+}
+
+pub fn part02(input: &str) -> Option<String> {
+    let mut total = 0;
+
+    for line in input.lines() {
+        let digits = (0..line.len())
+            .filter_map(|i| {
+                let rest = &line[i..];
+                rest.chars().next()?.to_digit(10).or_else(|| {
+                    WORDS
+                        .iter()
+                        .position(|w| rest.starts_with(w))
+                        .map(|p| p as u32 + 1)
+                })
+            })
+            .collect();
+
+        total += calibration(digits)?;
+    }
+
+    Some(total.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    #[test]
+    fn solutions_match_oracle() {
+        property::assert_day(&crate::registry(), 1, &[part01, part02], 30);
+    }
+}
@@ -1,13 +1,15 @@
+use aoc_common::math;
+
 pub fn process() -> usize {
     let input = include_str!("./input.txt");
 
     resolve_total_calibrations(input)
 }
 
-fn resolve_total_calibrations(input: &str) -> usize {
+pub fn resolve_total_calibrations(input: &str) -> usize {
     let calibrations = resolve_calibrations(input);
 
-    calibrations.iter().fold(0, |acc, n| acc + n)
+    math::checked_sum(calibrations).expect("Total calibration overflows")
 }
 
 fn resolve_calibrations(input: &str) -> Vec<usize> {
@@ -48,11 +50,6 @@ pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet"#;
 
-    #[test]
-    fn test_process() {
-        assert_eq!(process(), 54632);
-    }
-
     #[test]
     fn demo_result_ok() {
         let total = resolve_total_calibrations(INPUT_DEMO);
@@ -1,13 +1,15 @@
+use aoc_common::math;
+
 pub fn process() -> usize {
     let input = include_str!("./input.txt");
 
     resolve_total_calibrations(input)
 }
 
-fn resolve_total_calibrations(input: &str) -> usize {
+pub fn resolve_total_calibrations(input: &str) -> usize {
     let calibrations = resolve_calibrations(input);
 
-    calibrations.iter().fold(0, |acc, n| acc + n)
+    math::checked_sum(calibrations).expect("Total calibration overflows")
 }
 
 fn resolve_calibrations(input: &str) -> Vec<usize> {
@@ -88,11 +90,6 @@ xtwone3four
 zoneight234
 7pqrstsixteen"#;
 
-    #[test]
-    fn test_process() {
-        assert_eq!(process(), 54019);
-    }
-
     #[test]
     fn demo_result_ok() {
         let total = resolve_total_calibrations(INPUT_DEMO);
@@ -0,0 +1,43 @@
+use aoc_common::random::Rng;
+
+// Record of `size` games, each with 1 to 6 sets of revealed cubes in the
+// format `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut out = String::new();
+
+    for id in 1..=size.max(1) {
+        let sets: Vec<String> = (0..rng.range(1, 6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+
+                colors[..rng.range(1, 3) as usize]
+                    .iter()
+                    .map(|color| format!("{} {}", rng.range(1, 20), color))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+
+        out.push_str(&format!("Game {}: {}\n", id, sets.join("; ")));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day02;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_eq!(input.lines().count(), 50);
+
+        day02::part01::get_total_ids_possible_games(&input);
+        day02::part02::get_total_ids_possible_games(&input);
+    }
+}
@@ -0,0 +1,65 @@
+// Brute-force reference solutions, checked against the optimized ones over
+// generated inputs.
+
+// Games as their ID and the cubes of every set in the (red, green, blue) order.
+fn games(input: &str) -> Option<Vec<(u32, Vec<[u32; 3]>)>> {
+    input
+        .lines()
+        .map(|line| {
+            let (game, sets) = line.split_once(": ")?;
+            let id = game.strip_prefix("Game ")?.parse().ok()?;
+
+            let sets = sets
+                .split("; ")
+                .map(|set| {
+                    let mut cubes = [0; 3];
+                    for cube in set.split(", ") {
+                        let (count, color) = cube.split_once(' ')?;
+                        let i = ["red", "green", "blue"].iter().position(|c| *c == color)?;
+                        cubes[i] = count.parse().ok()?;
+                    }
+                    Some(cubes)
+                })
+                .collect::<Option<_>>()?;
+
+            Some((id, sets))
+        })
+        .collect()
+}
+
+pub fn part01(input: &str) -> Option<String> {
+    let total: u32 = games(input)?
+        .iter()
+        .filter(|(_, sets)| {
+            sets.iter()
+                .all(|[red, green, blue]| *red <= 12 && *green <= 13 && *blue <= 14)
+        })
+        .map(|(id, _)| id)
+        .sum();
+
+    Some(total.to_string())
+}
+
+pub fn part02(input: &str) -> Option<String> {
+    let total: u32 = games(input)?
+        .iter()
+        .map(|(_, sets)| {
+            (0..3)
+                .map(|i| sets.iter().map(|set| set[i]).max().unwrap_or(0))
+                .product::<u32>()
+        })
+        .sum();
+
+    Some(total.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    #[test]
+    fn solutions_match_oracle() {
+        property::assert_day(&crate::registry(), 2, &[part01, part02], 30);
+    }
+}
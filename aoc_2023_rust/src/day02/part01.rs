@@ -1,3 +1,6 @@
+use aoc_common::math;
+use aoc_common::pattern::FromLine;
+use aoc_common::trace;
 use std::collections::HashMap;
 
 pub fn process() -> usize {
@@ -6,10 +9,10 @@ pub fn process() -> usize {
     get_total_ids_possible_games(input)
 }
 
-fn get_total_ids_possible_games(input: &str) -> usize {
+pub fn get_total_ids_possible_games(input: &str) -> usize {
     let calibrations = get_ids_possible_games(input);
 
-    calibrations.iter().fold(0, |acc, n| acc + n)
+    math::checked_sum(calibrations).expect("Total overflows")
 }
 
 fn get_ids_possible_games(input: &str) -> Vec<usize> {
@@ -18,56 +21,55 @@ fn get_ids_possible_games(input: &str) -> Vec<usize> {
     let mut games: Vec<usize> = vec![];
 
     for line in input.lines() {
-        let mut game_id = 0;
-
-        for gps in line.split(";") {
-            for gp in gps.split(",") {
-                for section in gp.split(":") {
-                    let mut parts = section.split(" ");
-
-                    let mut first = parts.next().expect("Could not get key part");
-                    if first == "" {
-                        first = parts.next().expect("Could not get key part");
-                    }
-
-                    let second = parts.next().expect("Could not get value part");
-
-                    let key: &str;
-                    let val: &str;
-
-                    if first == "Game" {
-                        key = first;
-                        val = second;
-                    } else {
-                        key = second;
-                        val = first;
-                    }
-
-                    let num = val.parse::<usize>().expect("Could not parse number part");
-
-                    match key {
-                        "Game" => game_id = num,
-                        _ => {
-                            let check_num = check_game.get(key).expect("Invalid key");
-                            if num > *check_num {
-                                game_id = 0;
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let game = parse_game(line);
+
+        let possible = game.cubes().all(|cubes| {
+            let check_num = check_game.get(cubes.color.as_str()).expect("Invalid key");
+            cubes.count <= *check_num
+        });
 
-        if game_id > 0 {
-            // println!("game: {:?}", game_id);
-            games.push(game_id);
+        if possible {
+            trace!("game: {:?}", game.id);
+            games.push(game.id);
         }
     }
 
     games
 }
 
+// A count of cubes of a single color, e.g. `3 blue`
+#[derive(Debug, PartialEq, FromLine)]
+#[pattern("{count} {color}")]
+pub struct Cubes {
+    pub count: usize,
+    pub color: String,
+}
+
+// The cubes revealed at once, e.g. `3 blue, 4 red`
+#[derive(Debug, PartialEq, FromLine)]
+#[pattern("{cubes:, }")]
+pub struct Round {
+    pub cubes: Vec<Cubes>,
+}
+
+#[derive(Debug, PartialEq, FromLine)]
+#[pattern("Game {id}: {rounds:; }")]
+pub struct Game {
+    pub id: usize,
+    pub rounds: Vec<Round>,
+}
+
+impl Game {
+    // Every count of cubes revealed in the game, whatever the round
+    pub fn cubes(&self) -> impl Iterator<Item = &Cubes> {
+        self.rounds.iter().flat_map(|round| &round.cubes)
+    }
+}
+
+pub fn parse_game(line: &str) -> Game {
+    Game::from_line(line).expect("Invalid game")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,17 +80,25 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#;
 
-    #[test]
-    fn test_process() {
-        assert_eq!(process(), 2105);
-    }
-
     #[test]
     fn demo_result_ok() {
         let total = get_total_ids_possible_games(INPUT_DEMO);
         assert_eq!(total, 8);
     }
 
+    #[test]
+    fn demo_parse_game() {
+        let game = parse_game("Game 12: 3 blue, 4 red; 0 green");
+        assert_eq!(game.id, 12);
+        assert_eq!(game.rounds.len(), 2);
+
+        let cubes: Vec<(usize, &str)> = game
+            .cubes()
+            .map(|cubes| (cubes.count, cubes.color.as_str()))
+            .collect();
+        assert_eq!(cubes, vec![(3, "blue"), (4, "red"), (0, "green")]);
+    }
+
     #[test]
     fn demo_games() {
         let calibrations = get_ids_possible_games(INPUT_DEMO);
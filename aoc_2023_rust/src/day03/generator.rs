@@ -0,0 +1,49 @@
+use aoc_common::random::Rng;
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+
+// Engine schematic of `size` x `size` cells (at least 3) with numbers of 1 to
+// 3 digits and symbols spread over the empty `.` cells.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let width = size.max(3);
+    let mut out = String::new();
+
+    for _ in 0..width {
+        let mut line = String::new();
+
+        while line.len() < width {
+            match rng.range(0, 9) {
+                0..=5 => line.push('.'),
+                6 => line.push(*rng.choose(&SYMBOLS)),
+                _ => {
+                    line.push_str(&rng.range(1, 999).to_string());
+                    line.push('.');
+                }
+            }
+        }
+
+        line.truncate(width);
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day03;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_eq!(input.lines().count(), 50);
+        assert!(input.lines().all(|l| l.len() == 50));
+
+        day03::part01::get_total_engine_part_numbers(&input);
+        day03::part02::get_total_engine_part_numbers(&input);
+    }
+}
@@ -0,0 +1,126 @@
+// Brute-force reference solutions, checked against the optimized ones over
+// generated inputs. Every cell around every digit is checked on the grid.
+
+struct Number {
+    value: u32,
+    row: usize,
+    start: usize,
+    end: usize,
+}
+
+fn grid(input: &str) -> Option<Vec<Vec<char>>> {
+    let grid: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+
+    let width = grid.first()?.len();
+    let valid = grid.iter().all(|row| {
+        row.len() == width
+            && row
+                .iter()
+                .all(|c| c.is_ascii_digit() || c.is_ascii_punctuation())
+    });
+
+    match valid && width > 0 {
+        true => Some(grid),
+        false => None,
+    }
+}
+
+fn numbers(grid: &[Vec<char>]) -> Vec<Number> {
+    let mut numbers = vec![];
+
+    for (row, cells) in grid.iter().enumerate() {
+        let mut col = 0;
+        while col < cells.len() {
+            if !cells[col].is_ascii_digit() {
+                col += 1;
+                continue;
+            }
+
+            let start = col;
+            while col < cells.len() && cells[col].is_ascii_digit() {
+                col += 1;
+            }
+
+            let value = cells[start..col]
+                .iter()
+                .collect::<String>()
+                .parse()
+                .unwrap();
+            numbers.push(Number {
+                value,
+                row,
+                start,
+                end: col - 1,
+            });
+        }
+    }
+
+    numbers
+}
+
+fn is_adjacent(number: &Number, row: usize, col: usize) -> bool {
+    row + 1 >= number.row
+        && row <= number.row + 1
+        && col + 1 >= number.start
+        && col <= number.end + 1
+}
+
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+pub fn part01(input: &str) -> Option<String> {
+    let grid = grid(input)?;
+
+    let total: u32 = numbers(&grid)
+        .iter()
+        .filter(|n| {
+            grid.iter().enumerate().any(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .any(|(col, c)| is_symbol(*c) && is_adjacent(n, row, col))
+            })
+        })
+        .map(|n| n.value)
+        .sum();
+
+    Some(total.to_string())
+}
+
+pub fn part02(input: &str) -> Option<String> {
+    let grid = grid(input)?;
+    let numbers = numbers(&grid);
+    let mut total = 0;
+
+    for (row, cells) in grid.iter().enumerate() {
+        for (col, c) in cells.iter().enumerate() {
+            if *c != '*' {
+                continue;
+            }
+
+            let close: Vec<u32> = numbers
+                .iter()
+                .filter(|n| is_adjacent(n, row, col))
+                .map(|n| n.value)
+                .collect();
+
+            if close.len() == 2 {
+                total += close[0] * close[1];
+            }
+        }
+    }
+
+    Some(total.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    #[test]
+    fn solutions_match_oracle() {
+        property::assert_day(&crate::registry(), 3, &[part01, part02], 20);
+    }
+}
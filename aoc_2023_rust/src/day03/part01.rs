@@ -1,166 +1,110 @@
+use std::ops::Range;
+
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::math;
+use aoc_common::trace;
+use serde::Serialize;
+
+use crate::error::SolutionError;
+
 pub fn process() -> usize {
     let input = include_str!("./input.txt");
 
     get_total_engine_part_numbers(input)
 }
 
-fn get_total_engine_part_numbers(input: &str) -> usize {
-    let numbers = get_engine_part_numbers(input);
-
-    numbers.iter().fold(0, |acc, n| acc + n)
+pub fn get_total_engine_part_numbers(input: &str) -> usize {
+    let schematic = parse_schematic(input).expect("Invalid engine schematic");
+    solve(schematic).expect("Could not total the part numbers")
 }
 
-fn get_engine_part_numbers(input: &str) -> Vec<usize> {
-    let engine_part_lines = parse_engine_parts(input);
+pub fn solve(schematic: Schematic) -> Result<usize, SolutionError> {
+    let numbers = get_engine_part_numbers(&schematic);
 
-    let mut part_numbers = vec![];
+    math::checked_sum(numbers).map_err(SolutionError::MathErr)
+}
 
-    let line_len = input
-        .lines()
-        .next()
-        .expect("Could not get first line")
-        .len();
+fn get_engine_part_numbers(schematic: &Schematic) -> Vec<usize> {
+    let grid = &schematic.grid;
+
+    schematic
+        .numbers
+        .iter()
+        .filter(|number| {
+            number
+                .positions()
+                .flat_map(|pos| grid.neighbors8(pos))
+                .any(|pos| is_symbol(grid[pos]))
+        })
+        .map(|number| number.value)
+        .collect()
+}
 
-    for (line_index, line_parts) in engine_part_lines.iter().enumerate() {
-        let curr_line = &engine_part_lines[line_index];
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
 
-        for part in line_parts {
-            if let Some(num) = part.number {
-                // check current line
-                if has_symbol_close(&curr_line, &part, &line_len) {
-                    part_numbers.push(num);
-                    continue;
-                }
+// Numbers are the runs of digits of each row
+pub fn parse_schematic(input: &str) -> Result<Schematic, SolutionError> {
+    let grid = Grid::from_chars(input).map_err(SolutionError::InvalidGridErr)?;
+    let mut numbers = vec![];
 
-                // check previous line
-                if line_index > 0 {
-                    let prev_line = &engine_part_lines[line_index - 1];
-                    if has_symbol_close(&prev_line, &part, &line_len) {
-                        part_numbers.push(num);
-                        continue;
-                    }
-                }
+    for (row, cells) in grid.rows().enumerate() {
+        let mut col = 0;
 
-                // check next line
-                if line_index < engine_part_lines.len() - 1 {
-                    let next_line = &engine_part_lines[line_index + 1];
-                    if has_symbol_close(&next_line, &part, &line_len) {
-                        part_numbers.push(num);
-                        continue;
-                    }
-                }
+        while col < cells.len() {
+            if !cells[col].is_ascii_digit() {
+                col += 1;
+                continue;
             }
-        }
-    }
-
-    part_numbers
-}
 
-fn has_symbol_close(
-    line_parts: &Vec<EnginePart>,
-    number_part: &EnginePart,
-    line_len: &usize,
-) -> bool {
-    // extend range beginning to match any close record
-    let number_part_index_0 = if number_part.index_range.0 > 0 {
-        number_part.index_range.0 - 1
-    } else {
-        number_part.index_range.0
-    };
-
-    // extend range ending to match any close record
-    let number_part_index_1 = if number_part.index_range.1 < (line_len - 1) {
-        number_part.index_range.1 + 1
-    } else {
-        number_part.index_range.1
-    };
-
-    for symbol_part in line_parts {
-        if symbol_part.symbol.is_some() {
-            let has_insersection_begin = number_part_index_0 <= symbol_part.index_range.0
-                && number_part_index_1 >= symbol_part.index_range.0;
-
-            let has_insersection_end = number_part_index_0 <= symbol_part.index_range.1
-                && number_part_index_1 >= symbol_part.index_range.1;
-
-            if has_insersection_begin || has_insersection_end {
-                return true;
+            let start = col;
+            let mut value: usize = 0;
+            while col < cells.len() && cells[col].is_ascii_digit() {
+                let digit = cells[col].to_digit(10).expect("Could not get digit") as usize;
+                value = value
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(digit))
+                    .ok_or_else(|| {
+                        SolutionError::InvalidGridErr(format!("number too big in row {}", row))
+                    })?;
+                col += 1;
             }
+
+            trace!("number={:?} row={:?} cols={:?}", value, row, start..col);
+            numbers.push(PartNumber {
+                value,
+                row,
+                cols: start..col,
+            });
         }
     }
 
-    false
+    Ok(Schematic { grid, numbers })
 }
 
-pub fn parse_engine_parts(input: &str) -> Vec<Vec<EnginePart>> {
-    let mut engine_parts: Vec<Vec<EnginePart>> = vec![];
-
-    for line in input.lines() {
-        let mut engine_parts_line: Vec<EnginePart> = vec![];
-
-        let mut i = 0;
-        let mut part = String::from("");
-        let mut chars = line.chars().peekable();
-
-        loop {
-            let c = match chars.next() {
-                Some(c) => c,
-                None => break,
-            };
-
-            let next_c = chars.peek();
-            // let last = next_c.is_none();
-
-            // check part is complete
-            if c != '.' {
-                let completed = match next_c {
-                    Some(nc) => c.is_digit(10) != nc.is_digit(10) || *nc == '.',
-                    None => true,
-                };
-
-                if completed {
-                    part = part + &c.to_string();
-                    // println!("part complete={:?} ", part);
-
-                    // println!("  i={:?} part.len={:?}", i, part.len());
-                    let number = part.parse::<usize>().ok();
-                    let symbol = match number {
-                        Some(_) => None,
-                        None => Some(part.chars().next().expect("Could not get symbol")),
-                    };
-
-                    engine_parts_line.push(EnginePart {
-                        number,
-                        symbol,
-                        index_range: (i - (part.len() - 1), i),
-                    });
-
-                    part = "".to_string();
-                } else {
-                    part = part + &c.to_string();
-                    // println!("part continue={:?}", part);
-                }
-            }
-
-            i += 1;
-        }
-
-        engine_parts.push(engine_parts_line);
-    }
+pub struct Schematic {
+    pub grid: Grid<char>,
+    pub numbers: Vec<PartNumber>,
+}
 
-    engine_parts
+#[derive(Debug, PartialEq, Serialize)]
+pub struct PartNumber {
+    pub value: usize,
+    pub row: usize,
+    pub cols: Range<usize>,
 }
 
-#[derive(Debug, PartialEq)]
-pub struct EnginePart {
-    pub number: Option<usize>,
-    pub symbol: Option<char>,
-    pub index_range: (usize, usize),
+impl PartNumber {
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.cols.clone().map(|col| Pos::new(self.row, col))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::{assert_parsed, fuzz};
 
     const INPUT_DEMO: &str = r#"467..114..
 ...*......
@@ -173,11 +117,6 @@ mod tests {
 ...$.*....
 .664.598.."#;
 
-    #[test]
-    fn test_process() {
-        assert_eq!(process(), 532428);
-    }
-
     #[test]
     fn demo_result_ok() {
         let total = get_total_engine_part_numbers(INPUT_DEMO);
@@ -186,12 +125,12 @@ mod tests {
 
     #[test]
     fn demo_get_engine_part_numbers() {
-        let numbers = get_engine_part_numbers(INPUT_DEMO);
+        let numbers = get_engine_part_numbers(&parse_schematic(INPUT_DEMO).unwrap());
         assert_eq!(numbers, vec![467, 35, 633, 617, 592, 755, 664, 598]);
     }
 
     #[test]
-    fn demo_parse_engine_parts() {
+    fn demo_parse_schematic() {
         // 0123456789
         // ----------
         // 467..114..
@@ -204,51 +143,36 @@ mod tests {
         // ......755.
         // ...$.*....
         // .664.598..
-        let engine_parts = parse_engine_parts(INPUT_DEMO);
-        assert_eq!(engine_parts.len(), 10);
-
-        // line 1
-        assert_eq!(
-            engine_parts[0],
-            vec![
-                EnginePart {
-                    number: Some(467),
-                    symbol: None,
-                    index_range: (0, 2)
-                },
-                EnginePart {
-                    number: Some(114),
-                    symbol: None,
-                    index_range: (5, 7)
-                }
-            ],
-        );
+        let schematic = parse_schematic(INPUT_DEMO).unwrap();
+        assert_eq!(schematic.grid.to_string(), format!("{}\n", INPUT_DEMO));
+        assert_parsed!("part_numbers", schematic.numbers);
+    }
 
-        // line 2
+    #[test]
+    fn ragged_schematic() {
         assert_eq!(
-            engine_parts[1],
-            vec![EnginePart {
-                number: None,
-                symbol: Some('*'),
-                index_range: (3, 3)
-            },],
+            parse_schematic("467..\n...*").err(),
+            Some(SolutionError::InvalidGridErr(
+                "row 1 has 4 cells instead of 5".to_string()
+            ))
         );
+    }
 
-        // line 3
-        assert_eq!(
-            engine_parts[2],
-            vec![
-                EnginePart {
-                    number: Some(35),
-                    symbol: None,
-                    index_range: (2, 3)
-                },
-                EnginePart {
-                    number: Some(633),
-                    symbol: None,
-                    index_range: (6, 8)
+    #[test]
+    fn fuzz_parse_schematic() {
+        fuzz::assert_target(
+            env!("CARGO_MANIFEST_DIR"),
+            "day03_parse_schematic",
+            |input| {
+                if let Ok(schematic) = parse_schematic(input) {
+                    let digits = schematic
+                        .numbers
+                        .iter()
+                        .map(|n| n.cols.len())
+                        .sum::<usize>();
+                    assert!(digits <= input.len());
                 }
-            ],
+            },
         );
     }
 }
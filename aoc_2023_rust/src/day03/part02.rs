@@ -1,4 +1,7 @@
-use super::part01::{parse_engine_parts, EnginePart};
+use aoc_common::math;
+
+use super::part01::{parse_schematic, Schematic};
+use crate::error::SolutionError;
 
 pub fn process() -> usize {
     let input = include_str!("./input.txt");
@@ -6,93 +9,51 @@ pub fn process() -> usize {
     get_total_engine_part_numbers(input)
 }
 
-fn get_total_engine_part_numbers(input: &str) -> usize {
-    let numbers = get_engine_part_numbers(input);
-
-    numbers.iter().fold(0, |acc, n| acc + n)
+pub fn get_total_engine_part_numbers(input: &str) -> usize {
+    let schematic = parse_schematic(input).expect("Invalid engine schematic");
+    solve(schematic).expect("Could not total the gear ratios")
 }
 
-fn get_engine_part_numbers(input: &str) -> Vec<usize> {
-    let engine_part_lines = parse_engine_parts(input);
+pub fn solve(schematic: Schematic) -> Result<usize, SolutionError> {
+    let numbers = get_engine_part_numbers(&schematic)?;
 
-    let mut part_numbers = vec![];
+    math::checked_sum(numbers).map_err(SolutionError::MathErr)
+}
+
+fn get_engine_part_numbers(schematic: &Schematic) -> Result<Vec<usize>, SolutionError> {
+    let grid = &schematic.grid;
 
-    let line_len = input
-        .lines()
-        .next()
-        .expect("Could not get first line")
-        .len();
-
-    for (line_index, line_parts) in engine_part_lines.iter().enumerate() {
-        let curr_line = &engine_part_lines[line_index];
-
-        for part in line_parts {
-            if let Some(c) = part.symbol {
-                if c != '*' {
-                    continue;
-                }
-
-                let mut numbers: Vec<usize> = vec![];
-
-                // check previous line
-                if line_index > 0 {
-                    let prev_line = &engine_part_lines[line_index - 1];
-                    collect_numbers_close(&prev_line, &part, &line_len, &mut numbers);
-                }
-
-                // check current line
-                collect_numbers_close(&curr_line, &part, &line_len, &mut numbers);
-
-                // check next line
-                if line_index < engine_part_lines.len() - 1 {
-                    let next_line = &engine_part_lines[line_index + 1];
-                    collect_numbers_close(&next_line, &part, &line_len, &mut numbers);
-                }
-
-                if numbers.len() > 1 {
-                    let total = numbers.iter().fold(1, |acc, n| acc * n);
-                    part_numbers.push(total);
-                }
-            }
+    // index of the number covering each cell
+    let mut number_cells = grid.map(|_| None);
+    for (index, number) in schematic.numbers.iter().enumerate() {
+        for pos in number.positions() {
+            number_cells[pos] = Some(index);
         }
     }
 
-    part_numbers
-}
+    let mut part_numbers = vec![];
 
-fn collect_numbers_close(
-    line_parts: &Vec<EnginePart>,
-    symbol_part: &EnginePart,
-    line_len: &usize,
-    numbers: &mut Vec<usize>,
-) {
-    // extend range beginning to match any close record
-    let symbol_part_index_0 = if symbol_part.index_range.0 > 0 {
-        symbol_part.index_range.0 - 1
-    } else {
-        symbol_part.index_range.0
-    };
-
-    // extend range ending to match any close record
-    let symbol_part_index_1 = if symbol_part.index_range.1 < (line_len - 1) {
-        symbol_part.index_range.1 + 1
-    } else {
-        symbol_part.index_range.1
-    };
-
-    for number_part in line_parts {
-        if let Some(n) = number_part.number {
-            let has_insersection_begin = symbol_part_index_0 <= number_part.index_range.0
-                && symbol_part_index_1 >= number_part.index_range.0;
-
-            let has_insersection_end = symbol_part_index_0 <= number_part.index_range.1
-                && symbol_part_index_1 >= number_part.index_range.1;
-
-            if has_insersection_begin || has_insersection_end {
-                numbers.push(n);
-            }
+    for (pos, c) in grid.iter() {
+        if *c != '*' {
+            continue;
+        }
+
+        let mut close: Vec<usize> = grid
+            .neighbors8(pos)
+            .filter_map(|pos| number_cells[pos])
+            .collect();
+        close.sort();
+        close.dedup();
+
+        // A gear is adjacent to exactly two part numbers
+        if close.len() == 2 {
+            let total = math::checked_product(close.iter().map(|i| schematic.numbers[*i].value))
+                .map_err(SolutionError::MathErr)?;
+            part_numbers.push(total);
         }
     }
+
+    Ok(part_numbers)
 }
 
 #[cfg(test)]
@@ -110,14 +71,21 @@ mod tests {
 ...$.*....
 .664.598.."#;
 
-    #[test]
-    fn test_process() {
-        assert_eq!(process(), 84051670);
-    }
-
     #[test]
     fn demo_result_ok() {
         let total = get_total_engine_part_numbers(INPUT_DEMO);
         assert_eq!(total, 467835);
     }
+
+    #[test]
+    fn gear_next_to_symbol() {
+        let input = "..764.\n.#*.87\n...501";
+        assert_eq!(get_total_engine_part_numbers(input), 764 * 501);
+    }
+
+    #[test]
+    fn gear_with_three_numbers() {
+        let input = "635...\n*741..\n476...";
+        assert_eq!(get_total_engine_part_numbers(input), 0);
+    }
 }
@@ -0,0 +1,53 @@
+use aoc_common::random::Rng;
+
+// Pile of `size` scratchcards with 10 winning numbers and 25 numbers you
+// have from 0 to 99, in the format `Card   1: 41 48 83 | 83 86  6`.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut out = String::new();
+
+    let mut numbers: Vec<u64> = (0..=99).collect();
+    let format = |numbers: &[u64]| {
+        numbers
+            .iter()
+            .map(|n| format!("{:>2}", n))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    for id in 1..=size.max(1) {
+        rng.shuffle(&mut numbers);
+        let winning = format(&numbers[..10]);
+
+        // The numbers you have share a random amount of the winning numbers
+        let matching = rng.range(0, 10) as usize;
+        rng.shuffle(&mut numbers[matching..]);
+        let mut have = numbers[..25].to_vec();
+        rng.shuffle(&mut have);
+
+        out.push_str(&format!(
+            "Card {:>3}: {} | {}\n",
+            id,
+            winning,
+            format(&have)
+        ));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day04;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_eq!(input.lines().count(), 50);
+
+        day04::part01::get_total_winnings(&input);
+        day04::part02::get_total_winnings(&input);
+    }
+}
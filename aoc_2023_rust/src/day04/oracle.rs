@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+// Brute-force reference solutions, checked against the optimized ones over
+// generated inputs.
+
+fn numbers(text: &str) -> Option<Vec<u32>> {
+    let numbers: Vec<u32> = text
+        .split_whitespace()
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+
+    // numbers of a card side are unique
+    match numbers.iter().collect::<HashSet<_>>().len() == numbers.len() {
+        true => Some(numbers),
+        false => None,
+    }
+}
+
+// Amount of winning numbers you have on each card.
+fn matches(input: &str) -> Option<Vec<usize>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (card, numbers_part) = line.split_once(':')?;
+            let id: usize = card.strip_prefix("Card")?.trim().parse().ok()?;
+            if id != i + 1 {
+                return None;
+            }
+
+            let (winning, have) = numbers_part.split_once('|')?;
+            let have = numbers(have)?;
+            Some(
+                numbers(winning)?
+                    .iter()
+                    .filter(|n| have.contains(n))
+                    .count(),
+            )
+        })
+        .collect()
+}
+
+pub fn part01(input: &str) -> Option<String> {
+    let total: usize = matches(input)?
+        .iter()
+        .map(|m| match m {
+            0 => 0,
+            m => 1 << (m - 1),
+        })
+        .sum();
+
+    Some(total.to_string())
+}
+
+pub fn part02(input: &str) -> Option<String> {
+    let matches = matches(input)?;
+    let mut copies = vec![1; matches.len()];
+
+    for (card, m) in matches.iter().enumerate() {
+        for next in card + 1..=card + m {
+            if next < copies.len() {
+                copies[next] += copies[card];
+            }
+        }
+    }
+
+    Some(copies.iter().sum::<usize>().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    #[test]
+    fn solutions_match_oracle() {
+        property::assert_day(&crate::registry(), 4, &[part01, part02], 30);
+    }
+}
@@ -1,4 +1,10 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
+
+use aoc_common::math::{self, MathError};
+use aoc_common::pattern::FromLine;
+use aoc_common::trace;
+
+use crate::error::SolutionError;
 
 pub fn process() -> usize {
     let input = include_str!("./input.txt");
@@ -6,60 +12,62 @@ pub fn process() -> usize {
     get_total_winnings(input)
 }
 
-fn get_total_winnings(input: &str) -> usize {
-    let winnings = get_winnings(input);
+pub fn get_total_winnings(input: &str) -> usize {
+    let winnings = get_winnings(input).expect("Invalid scratchcards");
+    solve(winnings).expect("Could not total the points")
+}
 
-    winnings
+pub fn solve(winnings: Vec<Vec<usize>>) -> Result<usize, SolutionError> {
+    // The first match makes the card worth one point and each match after
+    // the first doubles the point value of that card.
+    let points = winnings
         .iter()
-        .map(|nums| {
-            nums.iter().fold(0, |acc, _n| {
-                // The first match makes the card worth one point and each match
-                // after the first doubles the point value of that card.
-                if acc == 0 {
-                    return 1;
-                }
-                acc * 2
-            })
+        .map(|nums| match nums.len() {
+            0 => Ok(0),
+            matches => u32::try_from(matches - 1)
+                .ok()
+                .and_then(|doublings| 2usize.checked_pow(doublings))
+                .ok_or(MathError::Overflow),
         })
-        .fold(0, |acc, n| acc + n)
+        .collect::<Result<Vec<usize>, MathError>>()
+        .map_err(SolutionError::MathErr)?;
+
+    math::checked_sum(points).map_err(SolutionError::MathErr)
 }
 
-pub fn get_winnings(input: &str) -> Vec<Vec<usize>> {
+#[derive(FromLine)]
+#[pattern("Card {id}: {winning: } | {numbers: }")]
+struct Card {
+    id: usize,
+    winning: Vec<usize>,
+    numbers: Vec<usize>,
+}
+
+pub fn get_winnings(input: &str) -> Result<Vec<Vec<usize>>, SolutionError> {
     let mut cards_winnings = vec![];
 
     for line in input.lines() {
-        let card = line.split(":").nth(1).expect("Could not get card data");
-
-        let mut card_parts = card.split("|");
+        let card = Card::from_line(line).map_err(SolutionError::InvalidLineErr)?;
 
-        let winnings_parts = card_parts.next().expect("Could not get winnings part");
-        let numbers_parts = card_parts.next().expect("Could not get numbers part");
+        let numbers_set: HashSet<usize> = card.numbers.into_iter().collect();
 
-        let winnings = winnings_parts
-            .split(" ")
-            .map(|n| n.parse::<usize>().unwrap_or(0))
-            .filter(|n| n > &0);
-
-        let numbers_hashmap: HashMap<usize, usize> = numbers_parts
-            .split(" ")
-            .map(|n| n.parse::<usize>().unwrap_or(0))
-            .filter(|n| n > &0)
-            .map(|n| (n, n))
-            .collect();
-
-        let matching: Vec<usize> = winnings
-            .filter(|w| numbers_hashmap.contains_key(w))
+        let matching: Vec<usize> = card
+            .winning
+            .into_iter()
+            .filter(|w| numbers_set.contains(w))
             .collect();
+        trace!("card {} matching {:?}", card.id, matching);
 
         cards_winnings.push(matching);
     }
 
-    cards_winnings
+    Ok(cards_winnings)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::fuzz;
 
     const INPUT_DEMO: &str = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
@@ -68,11 +76,6 @@ Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
 
-    #[test]
-    fn test_process() {
-        assert_eq!(process(), 22193);
-    }
-
     #[test]
     fn demo_result_ok() {
         let total = get_total_winnings(INPUT_DEMO);
@@ -81,7 +84,7 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
 
     #[test]
     fn demo_games() {
-        let winnings = get_winnings(INPUT_DEMO);
+        let winnings = get_winnings(INPUT_DEMO).unwrap();
         assert_eq!(
             winnings,
             vec![
@@ -103,7 +106,37 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
 
     #[test]
     fn demo_winnings_first_card() {
-        let winnings = get_winnings("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let winnings = get_winnings("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
         assert_eq!(winnings, vec![vec![48, 83, 86, 17]]);
     }
+
+    #[test]
+    fn zero_is_a_number() {
+        let winnings = get_winnings("Card 1: 0 5 | 0 7").unwrap();
+        assert_eq!(winnings, vec![vec![0]]);
+        assert!(get_winnings("Card 1: 99999999999999999999 | 1").is_err());
+    }
+
+    #[test]
+    fn overflowing_points_are_an_error() {
+        let bits = usize::BITS as usize;
+        assert_eq!(solve(vec![vec![1; bits]]), Ok(1 << (bits - 1)));
+        assert_eq!(
+            solve(vec![vec![1; bits + 1]]),
+            Err(SolutionError::MathErr(MathError::Overflow))
+        );
+        assert_eq!(
+            solve(vec![vec![1; bits], vec![1; bits]]),
+            Err(SolutionError::MathErr(MathError::Overflow))
+        );
+    }
+
+    #[test]
+    fn fuzz_get_winnings() {
+        fuzz::assert_target(env!("CARGO_MANIFEST_DIR"), "day04_get_winnings", |input| {
+            if let Ok(winnings) = get_winnings(input) {
+                assert!(winnings.iter().map(|w| w.len()).sum::<usize>() <= input.len());
+            }
+        });
+    }
 }
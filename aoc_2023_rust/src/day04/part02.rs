@@ -1,21 +1,28 @@
 use crate::day04::part01::get_winnings;
 use std::collections::HashMap;
 
+use aoc_common::math;
+
+use crate::error::SolutionError;
+
 pub fn process() -> usize {
     let input = include_str!("./input.txt");
 
     get_total_winnings(input)
 }
 
-fn get_total_winnings(input: &str) -> usize {
-    let winnings = get_winnings(input);
+pub fn get_total_winnings(input: &str) -> usize {
+    let winnings = get_winnings(input).expect("Invalid scratchcards");
+    solve(winnings).expect("Could not total the cards")
+}
 
+pub fn solve(winnings: Vec<Vec<usize>>) -> Result<usize, SolutionError> {
     let cards = dup_cards(&winnings);
 
-    cards.iter().fold(0, |acc, (_k, v)| acc + v)
+    math::checked_sum(cards.into_values()).map_err(SolutionError::MathErr)
 }
 
-fn dup_cards(winnings: &Vec<Vec<usize>>) -> HashMap<usize, usize> {
+fn dup_cards(winnings: &[Vec<usize>]) -> HashMap<usize, usize> {
     let mut cards: HashMap<usize, usize> = winnings
         .iter()
         .enumerate()
@@ -25,13 +32,10 @@ fn dup_cards(winnings: &Vec<Vec<usize>>) -> HashMap<usize, usize> {
     for (i, matches) in winnings.iter().enumerate() {
         let n_matches = matches.len();
         let card_id = i + 1;
-        let instances = cards
-            .get(&card_id)
-            .expect("Could not get instances")
-            .clone();
+        let instances = *cards.get(&card_id).expect("Could not get instances");
 
         let next_card_id = card_id + 1;
-        let end_match = &card_id + n_matches;
+        let end_match = card_id + n_matches;
 
         for line in next_card_id..=end_match {
             cards.entry(line).and_modify(|n| *n += instances);
@@ -41,6 +45,7 @@ fn dup_cards(winnings: &Vec<Vec<usize>>) -> HashMap<usize, usize> {
     cards
 }
 
+#[cfg(test)]
 fn pretty_cards(cards: &HashMap<usize, usize>) -> Vec<String> {
     let mut print_data = cards
         .iter()
@@ -63,12 +68,6 @@ Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
 
-    #[test]
-    fn test_process() {
-        // 18061 is not valid, too low
-        assert_eq!(process(), 5625994);
-    }
-
     #[test]
     fn demo_result_ok() {
         let total = get_total_winnings(INPUT_DEMO);
@@ -93,7 +92,7 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
         // Card 4: 08 instances
         // Card 5: 14 instances
         // Card 6: 01 instance
-        let winnings = get_winnings(INPUT_DEMO);
+        let winnings = get_winnings(INPUT_DEMO).unwrap();
         let cards = dup_cards(&winnings);
         let pretty = pretty_cards(&cards);
         assert_eq!(
@@ -111,7 +110,7 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
 
     #[test]
     fn demo_winnings_first_card() {
-        let winnings = get_winnings("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let winnings = get_winnings("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
         assert_eq!(winnings, vec![vec![48, 83, 86, 17]]);
     }
 }
@@ -0,0 +1,70 @@
+use aoc_common::random::Rng;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+// Almanac with 1 to 5 seed ranges and the 7 category maps, with numbers up to
+// `size * 100`. Seed ranges have at most `size` seeds, so the seeds of part 2
+// can still be expanded one by one, and some of them start at seed 0.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(1) as u64;
+    let limit = size * 100;
+
+    let seeds: Vec<String> = (0..rng.range(1, 5))
+        .map(|_| {
+            let start = match rng.chance(0.2) {
+                true => 0,
+                false => rng.range(1, limit),
+            };
+            format!("{} {}", start, rng.range(1, size))
+        })
+        .collect();
+
+    let mut out = format!("seeds: {}\n", seeds.join(" "));
+
+    for name in MAPS {
+        out.push_str(&format!("\n{} map:\n", name));
+
+        // Splits the numbers in consecutive source ranges, mapping some of them
+        let mut ranges = vec![];
+        let mut src = 0;
+        while src < limit {
+            let len = rng.range(1, limit - src);
+            if ranges.is_empty() || rng.chance(0.6) {
+                ranges.push((rng.range(0, limit), src, len));
+            }
+            src += len;
+        }
+
+        rng.shuffle(&mut ranges);
+        for (dst, src, len) in ranges {
+            out.push_str(&format!("{} {} {}\n", dst, src, len));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day05;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_eq!(input.lines().filter(|l| l.ends_with("map:")).count(), 7);
+
+        day05::part01::get_lowest_location(&input);
+        day05::part02::get_lowest_location(&input);
+    }
+}
@@ -1,2 +1,5 @@
+pub mod generator;
+pub mod oracle;
 pub mod part01;
 pub mod part02;
+pub mod part02_v2;
@@ -0,0 +1,93 @@
+// Brute-force reference solutions, checked against the optimized ones over
+// generated inputs. Every seed is walked through every map, one at a time.
+
+type Map = Vec<(u64, u64, u64)>;
+
+fn almanac(input: &str) -> Option<(Vec<u64>, Vec<Map>)> {
+    let mut blocks = input.trim_end_matches('\n').split("\n\n");
+
+    let seeds: Vec<u64> = blocks
+        .next()?
+        .strip_prefix("seeds: ")?
+        .split(' ')
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+
+    let maps = blocks
+        .map(|block| {
+            let mut lines = block.lines();
+            lines.next()?.strip_suffix(" map:")?;
+
+            let map: Map = lines
+                .map(
+                    |line| match line.split(' ').collect::<Vec<_>>().as_slice() {
+                        [dst, src, len] => {
+                            Some((dst.parse().ok()?, src.parse().ok()?, len.parse().ok()?))
+                        }
+                        _ => None,
+                    },
+                )
+                .collect::<Option<_>>()?;
+
+            // the source ranges of a map never overlap
+            let overlaps = map.iter().enumerate().any(|(i, a)| {
+                map[i + 1..]
+                    .iter()
+                    .any(|b| a.1 < b.1 + b.2 && b.1 < a.1 + a.2)
+            });
+
+            match overlaps {
+                true => None,
+                false => Some(map),
+            }
+        })
+        .collect::<Option<_>>()?;
+
+    Some((seeds, maps))
+}
+
+fn location(seed: u64, maps: &[Map]) -> u64 {
+    maps.iter().fold(seed, |target, map| {
+        map.iter()
+            .find(|(_, src, len)| target >= *src && target < src + len)
+            .map(|(dst, src, _)| dst + (target - src))
+            .unwrap_or(target)
+    })
+}
+
+pub fn part01(input: &str) -> Option<String> {
+    let (seeds, maps) = almanac(input)?;
+
+    seeds
+        .iter()
+        .map(|seed| location(*seed, &maps))
+        .min()
+        .map(|l| l.to_string())
+}
+
+pub fn part02(input: &str) -> Option<String> {
+    let (seeds, maps) = almanac(input)?;
+
+    // keep the seeds to walk through bounded
+    if seeds.len() % 2 != 0 || seeds.chunks(2).map(|s| s[1]).sum::<u64>() > 1_000_000 {
+        return None;
+    }
+
+    seeds
+        .chunks(2)
+        .flat_map(|s| s[0]..s[0] + s[1])
+        .map(|seed| location(seed, &maps))
+        .min()
+        .map(|l| l.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    #[test]
+    fn solutions_match_oracle() {
+        property::assert_day(&crate::registry(), 5, &[part01, part02], 20);
+    }
+}
@@ -1,115 +1,139 @@
+use aoc_common::group::{Grouping, Section};
+use aoc_common::interval::Interval;
+use aoc_common::{debug, numbers, trace};
+use serde::Serialize;
+
+use crate::error::SolutionError;
+
 pub fn process() -> usize {
     let input = include_str!("./input.txt");
 
     get_lowest_location(input)
 }
 
-fn get_lowest_location(input: &str) -> usize {
-    let mut locations = get_seeds_locations(input);
-    locations.sort();
-    locations[0]
+pub fn get_lowest_location(input: &str) -> usize {
+    let garden_info = parse_garden_info(input).expect("Invalid garden info");
+    solve(garden_info).expect("Could not find the lowest location")
 }
 
-fn get_seeds_locations(input: &str) -> Vec<usize> {
-    let (seeds, info_maps) = parse_garden_info(input);
+pub fn solve((seeds, info_maps): GardenInfo) -> Result<usize, SolutionError> {
+    let locations = get_seeds_locations(&seeds, &info_maps);
 
+    let lowest = locations
+        .into_iter()
+        .min()
+        .ok_or(SolutionError::MissingSeedErr)?;
+    lowest_location(lowest)
+}
+
+// Locations are only counted up from zero
+pub fn lowest_location(location: i64) -> Result<usize, SolutionError> {
+    usize::try_from(location).map_err(|_| SolutionError::NegativeLocationErr(location))
+}
+
+fn get_seeds_locations(seeds: &[i64], info_maps: &[InfoMap]) -> Vec<i64> {
     let mut locations = vec![];
 
-    for seed in seeds {
-        // println!("Seed {seed}");
+    for &seed in seeds {
+        trace!("Seed {seed}");
 
         let mut target = seed;
 
-        for info_map in &info_maps {
-            // println!("  Resolving {}", info_map.name);
-            for (dst, src, len) in &info_map.infos {
-                if target >= *src && target <= *src + len {
-                    target = dst + (target - src);
-                    // println!("    Found target {target} moving to the next map (dst={dst} src={src} len={len})");
-                    break;
-                }
-            }
+        for info_map in info_maps {
+            trace!("  Resolving {}", info_map.name);
+            target = info_map.resolve(target);
         }
 
         locations.push(target);
     }
-    // println!("locations {:?}", locations);
+    debug!("locations {:?}", locations);
 
     locations
 }
 
-#[derive(Debug, PartialEq)]
-struct InfoMap {
-    name: String,
-    infos: Vec<(usize, usize, usize)>,
+// Source values of a map line moved by the offset to their destination
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Mapping {
+    pub source: Interval,
+    pub offset: i64,
 }
 
-fn parse_garden_info(input: &str) -> (Vec<usize>, Vec<InfoMap>) {
-    let mut seeds = vec![];
-    let mut info_maps = vec![];
-
-    let mut info_map = InfoMap {
-        name: "".to_string(),
-        infos: vec![],
-    };
-
-    for line in input.lines() {
-        // Process first line which contains the seeds list
-        if line.starts_with("seeds:") {
-            let num_part = line
-                .split(":")
-                .nth(1)
-                .expect("Could not get seeds numbers part");
-
-            seeds = num_part
-                .split(" ")
-                .map(|n| n.parse::<usize>().unwrap_or(0))
-                .filter(|n| n > &0)
-                .collect();
-
-            continue;
-        }
+#[derive(Debug, PartialEq, Serialize)]
+pub struct InfoMap {
+    pub name: String,
+    pub mappings: Vec<Mapping>,
+}
 
-        // Detected it will start on the next iteration to process a new info map section
-        if line.trim() == "" {
-            if info_map.name != "" {
-                info_maps.push(info_map);
+impl InfoMap {
+    // Values outside every mapping keep their number
+    pub fn resolve(&self, value: i64) -> i64 {
+        match self.mappings.iter().find(|m| m.source.contains(value)) {
+            Some(mapping) => {
+                trace!("    Found {value} in {:?}", mapping);
+                value + mapping.offset
             }
+            None => value,
+        }
+    }
+}
 
-            // Reset info map to start processing a new one on the next line
-            info_map = InfoMap {
-                name: "".to_string(),
-                infos: vec![],
-            };
+// Parses a `destination source length` map line
+pub fn parse_mapping(line: &str) -> Result<Mapping, SolutionError> {
+    let nums: Vec<i64> = numbers::values(line).map_err(SolutionError::InvalidNumberErr)?;
 
-            continue;
-        }
+    let [dst, src, len] = nums[..] else {
+        return Err(SolutionError::InvalidMapLineErr(line.to_string()));
+    };
+    let invalid = || SolutionError::InvalidMapLineErr(line.to_string());
+    let end = src.checked_add(len).ok_or_else(invalid)?;
+    let offset = dst.checked_sub(src).ok_or_else(invalid)?;
+    // Every value of the source has to fit once moved to the destination
+    dst.checked_add(len).ok_or_else(invalid)?;
+
+    Ok(Mapping {
+        source: Interval::new(src, end),
+        offset,
+    })
+}
 
-        if info_map.name == "" {
-            info_map.name = line.to_string().replace(":", "");
-            continue;
-        }
+// Seeds and the maps to resolve their locations
+pub type GardenInfo = (Vec<i64>, Vec<InfoMap>);
 
-        let nums: Vec<usize> = line
-            .split(" ")
-            .map(|n| n.parse::<usize>().unwrap_or(0))
-            .collect();
+pub fn parse_garden_info(input: &str) -> Result<GardenInfo, SolutionError> {
+    let mut seeds = vec![];
+    let mut info_maps = vec![];
 
-        if nums.len() != 3 {
-            panic!("Expected 3 numbers got {}", nums.len());
+    for section in input.lines().sections() {
+        // The seeds list is a section of its own without any rows
+        if let Some(num_part) = section.header.strip_prefix("seeds:") {
+            seeds = numbers::values(num_part).map_err(SolutionError::InvalidNumberErr)?;
+            continue;
         }
 
-        info_map.infos.push((nums[0], nums[1], nums[2]));
+        info_maps.push(parse_info_map(&section)?);
     }
 
-    info_maps.push(info_map);
+    Ok((seeds, info_maps))
+}
 
-    (seeds, info_maps)
+// Parses a `seed-to-soil map:` header and its map lines
+pub fn parse_info_map(section: &Section<&str>) -> Result<InfoMap, SolutionError> {
+    let mappings = section
+        .body
+        .iter()
+        .map(|line| parse_mapping(line))
+        .collect::<Result<_, _>>()?;
+
+    Ok(InfoMap {
+        name: section.header.replace(":", ""),
+        mappings,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::{assert_parsed, fuzz};
 
     const INPUT_DEMO: &str = r#"seeds: 79 14 55 13
 
@@ -145,11 +169,6 @@ humidity-to-location map:
 60 56 37
 56 93 4"#;
 
-    #[test]
-    fn process_result() {
-        assert_eq!(process(), 214922730);
-    }
-
     #[test]
     fn demo_result() {
         let location = get_lowest_location(INPUT_DEMO);
@@ -158,7 +177,8 @@ humidity-to-location map:
 
     #[test]
     fn demo_get_seeds_locations() {
-        let locations = get_seeds_locations(INPUT_DEMO);
+        let (seeds, info_maps) = parse_garden_info(INPUT_DEMO).unwrap();
+        let locations = get_seeds_locations(&seeds, &info_maps);
 
         // Expected locations:
         // * Seed 79, soil 81, fertilizer 81, water 81, light 74, temperature 78, humidity 78, location 82.
@@ -170,57 +190,59 @@ humidity-to-location map:
 
     #[test]
     fn demo_parse_garden_info() {
-        let (seeds, info_maps) = parse_garden_info(INPUT_DEMO);
+        let (seeds, info_maps) = parse_garden_info(INPUT_DEMO).unwrap();
         assert_eq!(seeds, vec![79, 14, 55, 13]);
         assert_eq!(info_maps.len(), 7);
+        assert_parsed!("garden_info", info_maps);
+    }
+
+    #[test]
+    fn range_end_is_exclusive() {
+        let input = "seeds: 20\n\nseed-to-soil map:\n100 10 10\n";
+        assert_eq!(get_lowest_location(input), 20);
+    }
+
+    #[test]
+    fn lowest_location_needs_seeds() {
+        let input = "seeds:\n\nseed-to-soil map:\n50 98 2\n";
+        let garden_info = parse_garden_info(input).unwrap();
+        assert!(garden_info.0.is_empty());
+        assert_eq!(solve(garden_info), Err(SolutionError::MissingSeedErr));
+    }
+
+    #[test]
+    fn negative_location_is_an_error() {
+        let input = "seeds: 5\n\nseed-to-soil map:\n-10 0 10\n";
         assert_eq!(
-            info_maps[0],
-            InfoMap {
-                name: "seed-to-soil map".to_string(),
-                infos: vec![(50, 98, 2), (52, 50, 48)]
-            }
-        );
-        assert_eq!(
-            info_maps[1],
-            InfoMap {
-                name: "soil-to-fertilizer map".to_string(),
-                infos: vec![(0, 15, 37), (37, 52, 2), (39, 0, 15)]
-            }
-        );
-        assert_eq!(
-            info_maps[2],
-            InfoMap {
-                name: "fertilizer-to-water map".to_string(),
-                infos: vec![(49, 53, 8), (0, 11, 42), (42, 0, 7), (57, 7, 4)]
-            }
-        );
-        assert_eq!(
-            info_maps[3],
-            InfoMap {
-                name: "water-to-light map".to_string(),
-                infos: vec![(88, 18, 7), (18, 25, 70)]
-            }
-        );
-        assert_eq!(
-            info_maps[4],
-            InfoMap {
-                name: "light-to-temperature map".to_string(),
-                infos: vec![(45, 77, 23), (81, 45, 19), (68, 64, 13)]
-            }
-        );
-        assert_eq!(
-            info_maps[5],
-            InfoMap {
-                name: "temperature-to-humidity map".to_string(),
-                infos: vec![(0, 69, 1), (1, 0, 69)]
-            }
+            solve(parse_garden_info(input).unwrap()),
+            Err(SolutionError::NegativeLocationErr(-5))
         );
-        assert_eq!(
-            info_maps[6],
-            InfoMap {
-                name: "humidity-to-location map".to_string(),
-                infos: vec![(60, 56, 37), (56, 93, 4)]
-            }
+    }
+
+    #[test]
+    fn map_line_past_the_end_is_an_error() {
+        assert!(parse_mapping("9223372036854775807 0 2").is_err());
+        assert!(parse_mapping("0 9223372036854775807 2").is_err());
+        assert!(parse_mapping("9223372036854775806 0 1").is_ok());
+    }
+
+    #[test]
+    fn zero_seed() {
+        let input = "seeds: 0 3\n\nseed-to-soil map:\n10 0 1\n";
+        assert_eq!(get_lowest_location(input), 3);
+    }
+
+    #[test]
+    fn fuzz_parse_garden_info() {
+        fuzz::assert_target(
+            env!("CARGO_MANIFEST_DIR"),
+            "day05_part01_parse_garden_info",
+            |input| {
+                if let Ok((seeds, info_maps)) = parse_garden_info(input) {
+                    assert!(seeds.len() <= input.len());
+                    assert!(info_maps.len() <= input.len() + 1);
+                }
+            },
         );
     }
 }
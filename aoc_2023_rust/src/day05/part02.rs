@@ -1,119 +1,81 @@
+use aoc_common::group::Grouping;
+use aoc_common::interval::Interval;
+use aoc_common::numbers;
+
+use super::part01::{lowest_location, parse_info_map, InfoMap};
+use crate::error::SolutionError;
+
 pub fn process() -> usize {
     let input = include_str!("./input.txt");
 
     get_lowest_location(input)
 }
 
-fn get_lowest_location(input: &str) -> usize {
-    let (seeds, info_maps) = parse_garden_info(input);
+pub fn get_lowest_location(input: &str) -> usize {
+    let garden_info = parse_garden_info(input).expect("Invalid garden info");
+    solve(garden_info).expect("Could not find the lowest location")
+}
 
-    let mut is_first = true;
-    let mut lowest_location = 0;
+pub fn solve((seeds, info_maps): GardenInfo) -> Result<usize, SolutionError> {
+    let mut lowest = None;
 
     // TODO: This logic is quite slow for large input sets.
     // Check if there is any possible performance improvements.
-    for seed in seeds {
+    for seed in seeds.iter().flat_map(|seeds| seeds.start..seeds.end) {
         let mut target = seed;
 
         for info_map in &info_maps {
-            for (dst, src, len) in &info_map.infos {
-                if target >= *src && target <= *src + len {
-                    target = dst + (target - src);
-                    break;
-                }
-            }
+            target = info_map.resolve(target);
         }
 
-        if is_first || target < lowest_location {
-            is_first = false;
-            lowest_location = target;
+        if lowest.is_none_or(|lowest| target < lowest) {
+            lowest = Some(target);
         }
     }
 
-    lowest_location
+    lowest_location(lowest.ok_or(SolutionError::MissingSeedErr)?)
 }
 
-#[derive(Debug, PartialEq)]
-struct InfoMap {
-    name: String,
-    infos: Vec<(usize, usize, usize)>,
-}
+// Seed ranges and the maps to resolve their locations
+pub type GardenInfo = (Vec<Interval>, Vec<InfoMap>);
 
-fn parse_garden_info(input: &str) -> (Vec<usize>, Vec<InfoMap>) {
+pub fn parse_garden_info(input: &str) -> Result<GardenInfo, SolutionError> {
     let mut seeds = vec![];
     let mut info_maps = vec![];
 
-    let mut info_map = InfoMap {
-        name: "".to_string(),
-        infos: vec![],
-    };
-
-    for line in input.lines() {
-        // Process first line which contains the seeds list
-        if line.starts_with("seeds:") {
-            let num_part = line
-                .split(":")
-                .nth(1)
-                .expect("Could not get seeds numbers part");
-
-            let mut seeds_infos = num_part
-                .split(" ")
-                .map(|n| n.parse::<usize>().unwrap_or(0))
-                .filter(|n| n > &0)
-                .peekable();
-
-            while seeds_infos.peek().is_some() {
-                let start = seeds_infos.next().expect("Could not get seed start");
-                let range = seeds_infos.next().expect("Could not get seed range");
-
-                for seed in start..start + range {
-                    seeds.push(seed);
-                }
-            }
-
-            continue;
-        }
+    for section in input.lines().sections() {
+        // The seeds list is a section of its own without any rows
+        if let Some(num_part) = section.header.strip_prefix("seeds:") {
+            let mut seeds_infos = numbers::values::<i64>(num_part)
+                .map_err(SolutionError::InvalidNumberErr)?
+                .into_iter();
 
-        // Detected it will start on the next iteration to process a new info map section
-        if line.trim() == "" {
-            if info_map.name != "" {
-                info_maps.push(info_map);
-            }
+            // Ranges are not expanded, it would allocate every seed of the input
+            while let Some(start) = seeds_infos.next() {
+                let len = seeds_infos
+                    .next()
+                    .ok_or(SolutionError::MissingSeedRangeErr)?;
 
-            // Reset info map to start processing a new one on the next line
-            info_map = InfoMap {
-                name: "".to_string(),
-                infos: vec![],
-            };
+                let end = start
+                    .checked_add(len)
+                    .ok_or(SolutionError::SeedRangeOverflowErr)?;
 
-            continue;
-        }
+                seeds.push(Interval::new(start, end));
+            }
 
-        if info_map.name == "" {
-            info_map.name = line.to_string().replace(":", "");
             continue;
         }
 
-        let nums: Vec<usize> = line
-            .split(" ")
-            .map(|n| n.parse::<usize>().unwrap_or(0))
-            .collect();
-
-        if nums.len() != 3 {
-            panic!("Expected 3 numbers got {}", nums.len());
-        }
-
-        info_map.infos.push((nums[0], nums[1], nums[2]));
+        info_maps.push(parse_info_map(&section)?);
     }
 
-    info_maps.push(info_map);
-
-    (seeds, info_maps)
+    Ok((seeds, info_maps))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::{assert_parsed, fuzz};
 
     const INPUT_DEMO: &str = r#"seeds: 79 14 55 13
 
@@ -149,11 +111,6 @@ humidity-to-location map:
 60 56 37
 56 93 4"#;
 
-    #[test]
-    fn process_result() {
-        assert_eq!(process(), 148041808);
-    }
-
     #[test]
     fn demo_result() {
         let location = get_lowest_location(INPUT_DEMO);
@@ -162,65 +119,23 @@ humidity-to-location map:
 
     #[test]
     fn demo_parse_garden_info() {
-        let (seeds, info_maps) = parse_garden_info(INPUT_DEMO);
-        assert_eq!(
-            seeds,
-            vec![
-                // first seeds line
-                79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92,
-                // second seeds line
-                55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67
-            ]
-        );
+        let (seeds, info_maps) = parse_garden_info(INPUT_DEMO).unwrap();
+        assert_eq!(seeds, vec![Interval::new(79, 93), Interval::new(55, 68)]);
         assert_eq!(info_maps.len(), 7);
-        assert_eq!(
-            info_maps[0],
-            InfoMap {
-                name: "seed-to-soil map".to_string(),
-                infos: vec![(50, 98, 2), (52, 50, 48)]
-            }
-        );
-        assert_eq!(
-            info_maps[1],
-            InfoMap {
-                name: "soil-to-fertilizer map".to_string(),
-                infos: vec![(0, 15, 37), (37, 52, 2), (39, 0, 15)]
-            }
-        );
-        assert_eq!(
-            info_maps[2],
-            InfoMap {
-                name: "fertilizer-to-water map".to_string(),
-                infos: vec![(49, 53, 8), (0, 11, 42), (42, 0, 7), (57, 7, 4)]
-            }
-        );
-        assert_eq!(
-            info_maps[3],
-            InfoMap {
-                name: "water-to-light map".to_string(),
-                infos: vec![(88, 18, 7), (18, 25, 70)]
-            }
-        );
-        assert_eq!(
-            info_maps[4],
-            InfoMap {
-                name: "light-to-temperature map".to_string(),
-                infos: vec![(45, 77, 23), (81, 45, 19), (68, 64, 13)]
-            }
-        );
-        assert_eq!(
-            info_maps[5],
-            InfoMap {
-                name: "temperature-to-humidity map".to_string(),
-                infos: vec![(0, 69, 1), (1, 0, 69)]
-            }
-        );
-        assert_eq!(
-            info_maps[6],
-            InfoMap {
-                name: "humidity-to-location map".to_string(),
-                infos: vec![(60, 56, 37), (56, 93, 4)]
-            }
+        assert_parsed!("garden_info", info_maps);
+    }
+
+    #[test]
+    fn fuzz_parse_garden_info() {
+        fuzz::assert_target(
+            env!("CARGO_MANIFEST_DIR"),
+            "day05_part02_parse_garden_info",
+            |input| {
+                if let Ok((seeds, info_maps)) = parse_garden_info(input) {
+                    assert!(seeds.len() <= input.len());
+                    assert!(info_maps.len() <= input.len() + 1);
+                }
+            },
         );
     }
 }
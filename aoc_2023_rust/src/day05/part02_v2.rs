@@ -0,0 +1,136 @@
+use aoc_common::interval::{Interval, RangeSet};
+use aoc_common::trace;
+
+use super::part01::{lowest_location, InfoMap};
+use super::part02::{parse_garden_info, GardenInfo};
+use crate::error::SolutionError;
+
+pub fn process() -> usize {
+    let input = include_str!("./input.txt");
+
+    get_lowest_location(input)
+}
+
+pub fn get_lowest_location(input: &str) -> usize {
+    let garden_info = parse_garden_info(input).expect("Invalid garden info");
+    solve(garden_info).expect("Could not find the lowest location")
+}
+
+// Moves whole seed ranges through the maps instead of every seed, splitting
+// them where they cross the edges of a mapping.
+pub fn solve((seeds, info_maps): GardenInfo) -> Result<usize, SolutionError> {
+    let mut ranges: RangeSet = seeds.into_iter().collect();
+
+    for info_map in &info_maps {
+        ranges = map_ranges(&ranges, info_map);
+        trace!("{} ranges={:?}", info_map.name, ranges.intervals());
+    }
+
+    let lowest = ranges
+        .intervals()
+        .first()
+        .ok_or(SolutionError::MissingSeedErr)?;
+    lowest_location(lowest.start)
+}
+
+fn map_ranges(ranges: &RangeSet, info_map: &InfoMap) -> RangeSet {
+    let mut mapped = vec![];
+    let mut pending: Vec<Interval> = ranges.intervals().to_vec();
+
+    for mapping in &info_map.mappings {
+        let mut rest = vec![];
+
+        for range in pending {
+            match range.intersection(&mapping.source) {
+                Some(inside) => {
+                    let moved = inside.translate(mapping.offset);
+                    mapped.push(moved.expect("Map lines are checked to fit when parsed"));
+
+                    let (before, after) = range.difference(&mapping.source);
+                    rest.extend(before);
+                    rest.extend(after);
+                }
+                None => rest.push(range),
+            }
+        }
+
+        pending = rest;
+    }
+
+    // Values outside every mapping keep their number
+    mapped.into_iter().chain(pending).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day05::part02;
+
+    const INPUT_DEMO: &str = r#"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4"#;
+
+    #[test]
+    fn demo_result() {
+        let location = get_lowest_location(INPUT_DEMO);
+        assert_eq!(location, 46);
+    }
+
+    #[test]
+    fn demo_map_ranges() {
+        let (_, info_maps) = parse_garden_info(INPUT_DEMO).unwrap();
+        let seeds: RangeSet = [Interval::new(79, 93)].into_iter().collect();
+
+        // 79..93 falls in `52 50 48`, moving by 2
+        let soil = map_ranges(&seeds, &info_maps[0]);
+        assert_eq!(soil.intervals(), &[Interval::new(81, 95)]);
+
+        // 60..75 is split at 69 and 70 by `0 69 1` and `1 0 69`, the rest
+        // keeps its numbers
+        let ranges: RangeSet = [Interval::new(60, 75)].into_iter().collect();
+        let humidity = map_ranges(&ranges, &info_maps[5]);
+        assert_eq!(
+            humidity.intervals(),
+            &[Interval::new(0, 1), Interval::new(61, 75)]
+        );
+    }
+
+    #[test]
+    fn split_ranges_match_part02() {
+        let input = "seeds: 1 30\n\nseed-to-soil map:\n100 5 5\n0 20 3\n";
+        assert_eq!(
+            get_lowest_location(input),
+            part02::get_lowest_location(input)
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use aoc_common::math::MathError;
+use aoc_common::numbers::NumberError;
+use aoc_common::pattern::PatternError;
+
+#[derive(Debug, PartialEq)]
+pub enum SolutionError {
+    MissingSeedErr,
+    MissingSeedRangeErr,
+    NegativeLocationErr(i64),
+    SeedRangeOverflowErr,
+    InvalidMapLineErr(String),
+    InvalidGridErr(String),
+    InvalidNumberErr(NumberError),
+    InvalidLineErr(PatternError),
+    MathErr(MathError),
+}
@@ -0,0 +1,123 @@
+use aoc_common::answers::Answers;
+use aoc_common::solution::{Generator, Registry, Solution};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod error;
+
+use error::SolutionError;
+
+pub const YEAR: u16 = 2023;
+
+fn solution(day: u8, part: u8, version: u8, process: fn(&str) -> usize) -> Solution {
+    Solution::new(YEAR, day, part, version, move |input| {
+        Ok(process(input).to_string())
+    })
+}
+
+// Registers a solution with its parse step, timed apart from solving.
+fn parsed_solution<T: 'static>(
+    day: u8,
+    part: u8,
+    version: u8,
+    parse: fn(&str) -> Result<T, SolutionError>,
+    solve: fn(T) -> Result<usize, SolutionError>,
+) -> Solution {
+    Solution::with_parse(
+        YEAR,
+        day,
+        part,
+        version,
+        move |input| parse(input).map_err(|err| format!("{:?}", err)),
+        move |parsed| {
+            solve(parsed)
+                .map(|answer| answer.to_string())
+                .map_err(|err| format!("{:?}", err))
+        },
+    )
+}
+
+pub fn registry() -> Registry {
+    let solutions = vec![
+        solution(1, 1, 1, day01::part01::resolve_total_calibrations),
+        solution(1, 2, 1, day01::part02::resolve_total_calibrations),
+        solution(2, 1, 1, day02::part01::get_total_ids_possible_games),
+        solution(2, 2, 1, day02::part02::get_total_ids_possible_games),
+        parsed_solution(
+            3,
+            1,
+            1,
+            day03::part01::parse_schematic,
+            day03::part01::solve,
+        ),
+        parsed_solution(
+            3,
+            2,
+            1,
+            day03::part01::parse_schematic,
+            day03::part02::solve,
+        ),
+        parsed_solution(4, 1, 1, day04::part01::get_winnings, day04::part01::solve),
+        parsed_solution(4, 2, 1, day04::part01::get_winnings, day04::part02::solve),
+        parsed_solution(
+            5,
+            1,
+            1,
+            day05::part01::parse_garden_info,
+            day05::part01::solve,
+        ),
+        parsed_solution(
+            5,
+            2,
+            1,
+            day05::part02::parse_garden_info,
+            day05::part02::solve,
+        ),
+        parsed_solution(
+            5,
+            2,
+            2,
+            day05::part02::parse_garden_info,
+            day05::part02_v2::solve,
+        ),
+    ];
+
+    let generators = vec![
+        Generator {
+            day: 1,
+            generate: day01::generator::generate,
+        },
+        Generator {
+            day: 2,
+            generate: day02::generator::generate,
+        },
+        Generator {
+            day: 3,
+            generate: day03::generator::generate,
+        },
+        Generator {
+            day: 4,
+            generate: day04::generator::generate,
+        },
+        Generator {
+            day: 5,
+            generate: day05::generator::generate,
+        },
+    ];
+
+    Registry {
+        year: YEAR,
+        src_dir: concat!(env!("CARGO_MANIFEST_DIR"), "/src"),
+        solutions,
+        answers: Answers::parse(include_str!("../answers.toml")).expect("Invalid answers.toml"),
+        generators,
+    }
+}
+
+#[cfg(test)]
+mod answer_tests {
+    include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+}
@@ -1,35 +1,49 @@
-use std::env;
+use aoc_2023_rust::registry;
+use aoc_common::format::Format;
+use aoc_common::runner::{self, RunArgs};
+use clap::Parser;
 
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Puzzle name (e.g. day01_part01), runs all puzzles when omitted
+    puzzle: Option<String>,
+
+    #[arg(long)]
+    demo: bool,
+
+    #[command(flatten)]
+    run: RunArgs,
+}
 
 fn main() {
-    println!("");
-    println!(":::::::::::::::::::::::::::");
-    println!("::: Advent of Code 2023 :::");
-    println!(":::::::::::::::::::::::::::");
-    println!("");
-
-    let puzzle = env::args()
-        .nth(1)
-        .expect("Missing puzzle name (e.g. day01_part01)");
-
-    let result = match puzzle.as_str() {
-        "day01_part01" => day01::part01::process(),
-        "day01_part02" => day01::part02::process(),
-        "day02_part01" => day02::part01::process(),
-        "day02_part02" => day02::part02::process(),
-        "day03_part01" => day03::part01::process(),
-        "day03_part02" => day03::part02::process(),
-        "day04_part01" => day04::part01::process(),
-        "day04_part02" => day04::part02::process(),
-        "day05_part01" => day05::part01::process(),
-        "day05_part02" => day05::part02::process(),
-        _ => panic!("Invalid puzzle {puzzle}"),
+    let args = Args::parse();
+
+    let registry = registry();
+    runner::serve(&registry, &args.run);
+
+    if args.run.format == Format::Text {
+        println!();
+        println!(":::::::::::::::::::::::::::");
+        println!("::: Advent of Code 2023 :::");
+        println!(":::::::::::::::::::::::::::");
+        println!();
+    }
+
+    let solutions: Vec<_> = match &args.puzzle {
+        Some(puzzle) => {
+            let s = registry
+                .solutions
+                .iter()
+                .find(|s| s.name() == *puzzle)
+                .unwrap_or_else(|| panic!("Invalid puzzle {puzzle}"));
+
+            vec![s]
+        }
+        None => registry.solutions.iter().collect(),
     };
 
-    println!(" => Result {puzzle}: {result}");
+    if !runner::run(&registry, &solutions, args.demo, &args.run) {
+        std::process::exit(1);
+    }
 }
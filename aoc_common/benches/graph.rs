@@ -0,0 +1,80 @@
+// Timings of the graph searches on a large random maze and graph, summarized
+// like the solution benchmarks. Run with:
+//
+//   cargo bench -p aoc_common --bench graph
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc_common::bench::Stats;
+use aoc_common::graph;
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::random::Rng;
+
+const SIZE: usize = 500;
+const WARMUP: usize = 2;
+const ITERATIONS: usize = 10;
+
+fn measure<T, F: FnMut() -> T>(name: &str, mut f: F) {
+    for _ in 0..WARMUP {
+        black_box(f());
+    }
+
+    let timings: Vec<Duration> = (0..ITERATIONS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    let stats = Stats::new(&timings);
+    println!(
+        "{:<32} median {:>9.3} ms  min {:>9.3} ms  ± {:.3} ms",
+        name, stats.median_ms, stats.min_ms, stats.stddev_ms
+    );
+}
+
+fn main() {
+    let mut rng = Rng::new(7);
+
+    // A maze of walls covering a quarter of the cells, with weights 1 to 9
+    let maze = Grid::new(SIZE, SIZE, 0).map(|_| match rng.chance(0.25) {
+        true => 0,
+        false => rng.range(1, 9) as u32,
+    });
+    let start = Pos::new(0, 0);
+    let goal = Pos::new(SIZE - 1, SIZE - 1);
+
+    let open = |&pos: &Pos| maze.neighbors4(pos).filter(|&n| maze[n] > 0);
+    let weighted = |&pos: &Pos| {
+        maze.neighbors4(pos)
+            .filter(|&n| maze[n] > 0)
+            .map(|n| (n, maze[n]))
+    };
+    let distance = |&pos: &Pos| ((goal.row - pos.row) + (goal.col - pos.col)) as u32;
+
+    measure("bfs maze", || graph::bfs(start, open).len());
+    measure("dfs maze", || graph::dfs(start, open).len());
+    measure("dijkstra maze", || graph::dijkstra(start, weighted).len());
+    measure("dijkstra_path maze", || {
+        graph::dijkstra_path(start, weighted, |&pos| pos == goal)
+    });
+    measure("astar maze", || {
+        graph::astar(start, weighted, distance, |&pos| pos == goal)
+    });
+
+    // Random directed graph with a few edges per node
+    let nodes = SIZE * SIZE / 4;
+    let edges: Vec<Vec<usize>> = (0..nodes)
+        .map(|_| (0..rng.range(0, 3)).map(|_| rng.index(nodes)).collect())
+        .collect();
+    let next = |&n: &usize| edges[n].iter().copied();
+    let forward = |&n: &usize| edges[n].iter().copied().filter(move |&m| m > n);
+
+    measure("strongly_connected_components", || {
+        graph::strongly_connected_components(0..nodes, next).len()
+    });
+    measure("topological_sort", || {
+        graph::topological_sort(0..nodes, forward).map(|sorted| sorted.len())
+    });
+}
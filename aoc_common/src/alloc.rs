@@ -0,0 +1,160 @@
+// Opt-in heap profiling of the solutions. With the `alloc-stats` feature the
+// counting allocator below becomes the global allocator and every solution
+// run reports its peak heap usage, the bytes it allocated and how many
+// allocations it made:
+//
+//   cargo run -p aoc --features alloc-stats -- bench --year 2023
+//
+// The counters are shared by all threads, so a run only measures itself when
+// nothing else allocates at the same time, as in the single threaded runners.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static TOTAL: AtomicUsize = AtomicUsize::new(0);
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// System allocator keeping count of the allocations
+pub struct Counting;
+
+impl Counting {
+    fn add(&self, size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        TOTAL.fetch_add(size, Ordering::Relaxed);
+        COUNT.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn remove(&self, size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.remove(layout.size());
+    }
+
+    // A reallocation counts as freeing the old block and allocating the new one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.remove(layout.size());
+            self.add(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+// Heap usage of a run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Memory {
+    // Most bytes in use at once above what was in use before the run
+    pub peak_bytes: usize,
+    pub allocated_bytes: usize,
+    pub allocations: usize,
+}
+
+pub fn enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+// Bytes currently allocated, always zero when the counting allocator is not
+// enabled.
+pub fn in_use() -> usize {
+    CURRENT.load(Ordering::Relaxed)
+}
+
+// Runs the function measuring its heap usage, None when the counting
+// allocator is not enabled.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Option<Memory>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    let (value, memory) = count(f);
+    (value, Some(memory))
+}
+
+fn count<T, F: FnOnce() -> T>(f: F) -> (T, Memory) {
+    let start = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(start, Ordering::Relaxed);
+    let total = TOTAL.load(Ordering::Relaxed);
+    let count = COUNT.load(Ordering::Relaxed);
+
+    let value = f();
+
+    let memory = Memory {
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(start),
+        allocated_bytes: TOTAL.load(Ordering::Relaxed) - total,
+        allocations: COUNT.load(Ordering::Relaxed) - count,
+    };
+
+    (value, memory)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Uses the allocator directly, so it also runs when it is not the global
+    // allocator of the tests
+    #[test]
+    #[cfg(not(feature = "alloc-stats"))]
+    fn counts_allocations() {
+        let layout = Layout::from_size_align(1024, 8).unwrap();
+
+        let ((), memory) = count(|| unsafe {
+            let a = Counting.alloc(layout);
+            let b = Counting.alloc_zeroed(layout);
+            Counting.dealloc(a, layout);
+            let b = Counting.realloc(b, layout, 4096);
+            Counting.dealloc(b, Layout::from_size_align(4096, 8).unwrap());
+        });
+
+        assert_eq!(
+            memory,
+            Memory {
+                peak_bytes: 4096,
+                allocated_bytes: 6144,
+                allocations: 3,
+            }
+        );
+        assert_eq!(measure(|| 1), (1, None));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn measures_allocations() {
+        let (len, memory) = measure(|| vec![0u8; 1 << 20].len());
+
+        let memory = memory.unwrap();
+        assert_eq!(len, 1 << 20);
+        assert!(memory.peak_bytes >= 1 << 20);
+        assert!(memory.allocations >= 1);
+    }
+}
@@ -0,0 +1,393 @@
+// Store of the known puzzle answers of a year, kept in `answers.toml`.
+//
+// Answers are keyed by day, part and input kind (demo or real), and record a
+// hash of the input they belong to, so a replaced input file is never checked
+// against an answer of another input.
+//
+//   [day04.part02.real]
+//   answer = "801"
+//   input_hash = "9c3b5f2c6f1e0a47"
+//
+// An entry can have an `ignore` reason to skip its generated test, e.g. for
+// solutions too slow to run on every `cargo test`. With `ignore_versions` it
+// only skips those versions of the solution, so a faster version still runs.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::input;
+use crate::solution::Registry;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub answer: String,
+    pub input_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore: Option<String>,
+    // Versions the ignore applies to, all of them when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_versions: Option<Vec<u8>>,
+}
+
+impl Entry {
+    // Reason to skip the answer for the version of the solution
+    pub fn ignored(&self, version: u8) -> Option<&str> {
+        match &self.ignore_versions {
+            Some(versions) if !versions.contains(&version) => None,
+            _ => self.ignore.as_deref(),
+        }
+    }
+}
+
+// Layout of the TOML file: day -> part -> demo|real -> entry
+type AnswersFile = BTreeMap<String, BTreeMap<String, BTreeMap<String, Entry>>>;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(u8, u8, bool), Entry>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let file: AnswersFile = toml::from_str(text).map_err(|err| err.to_string())?;
+        let mut answers = Answers::default();
+
+        for (day_key, parts) in file {
+            let day = parse_key(&day_key, "day")?;
+
+            for (part_key, inputs) in parts {
+                let part = parse_key(&part_key, "part")?;
+
+                for (input_key, entry) in inputs {
+                    let demo = match input_key.as_str() {
+                        "demo" => true,
+                        "real" => false,
+                        _ => return Err(format!("invalid input kind {:?}", input_key)),
+                    };
+
+                    answers.entries.insert((day, part, demo), entry);
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Answers, String> {
+        let text = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Answers::parse(&text)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut file = AnswersFile::new();
+
+        for ((day, part, demo), entry) in &self.entries {
+            file.entry(format!("day{:02}", day))
+                .or_default()
+                .entry(format!("part{:02}", part))
+                .or_default()
+                .insert(input_kind(*demo).to_string(), entry.clone());
+        }
+
+        toml::to_string(&file).expect("Could not serialize answers")
+    }
+
+    pub fn get(&self, day: u8, part: u8, demo: bool) -> Option<&Entry> {
+        self.entries.get(&(day, part, demo))
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, demo: bool, entry: Entry) {
+        self.entries.insert((day, part, demo), entry);
+    }
+
+    // Known answer for the given input, only when it was recorded for that same input.
+    pub fn expected(&self, day: u8, part: u8, demo: bool, input: &str) -> Option<&str> {
+        self.get(day, part, demo)
+            .filter(|e| e.input_hash == hash(input))
+            .map(|e| e.answer.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&(u8, u8, bool), &Entry)> {
+        self.entries.iter()
+    }
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u8, String> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("invalid key {:?}, expected {}NN", key, prefix))
+}
+
+pub fn input_kind(demo: bool) -> &'static str {
+    match demo {
+        true => "demo",
+        false => "real",
+    }
+}
+
+// FNV-1a hash of the input, stable across platforms and Rust versions.
+pub fn hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for byte in input.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    format!("{:016x}", hash)
+}
+
+// Runs a registered solution against an input with a known answer, used by
+// the generated answer tests.
+pub fn assert_answer(registry: &Registry, day: u8, part: u8, version: u8, demo: bool) {
+    let solution = registry.find(day, part, version).unwrap_or_else(|| {
+        panic!(
+            "Solution day={} part={} version={} is not registered",
+            day, part, version
+        )
+    });
+
+    let entry = registry
+        .answers
+        .get(day, part, demo)
+        .expect("Missing known answer");
+
+    let input = input::load(registry.src_dir, day, part, demo).expect("Could not read input");
+    assert_eq!(
+        hash(&input),
+        entry.input_hash,
+        "The {} input changed since the answer was recorded",
+        input_kind(demo)
+    );
+
+    let answer = solution.process(&input).expect("Solution failed");
+    assert_eq!(answer, entry.answer);
+}
+
+// Runs every registered version of a day part against an input with a known
+// answer, used by the generated answer tests. With `ignored` it runs only the
+// versions the entry ignores, otherwise only the others.
+pub fn assert_answers(registry: &Registry, day: u8, part: u8, demo: bool, ignored: bool) {
+    let entry = registry
+        .answers
+        .get(day, part, demo)
+        .expect("Missing known answer");
+
+    let versions: Vec<u8> = registry
+        .solutions
+        .iter()
+        .filter(|s| s.day == day && s.part == part)
+        .map(|s| s.version)
+        .collect();
+    assert!(
+        !versions.is_empty(),
+        "No solution registered for day={} part={}",
+        day,
+        part
+    );
+
+    for version in versions {
+        if entry.ignored(version).is_some() == ignored {
+            assert_answer(registry, day, part, version, demo);
+        }
+    }
+}
+
+// Generates the tests of every input with a known answer, each running the
+// solutions registered for its day and part. Entries with an `ignore` reason
+// get an ignored `_ignored` test running the versions they skip. Meant to be
+// called from the build script of a year crate, which includes the generated
+// file in its test module.
+pub fn generate_tests(answers: &Answers) -> String {
+    let mut out = String::new();
+
+    let mut entries: Vec<_> = answers.iter().collect();
+    // The demo input first, as in answers.toml
+    entries.sort_by_key(|&(&(day, part, demo), _)| (day, part, !demo));
+
+    for (&(day, part, demo), entry) in entries {
+        let name = format!("day{:02}_part{:02}_{}", day, part, input_kind(demo));
+
+        // Entries ignoring every version leave nothing to run unignored
+        if entry.ignore.is_none() || entry.ignore_versions.is_some() {
+            writeln!(
+                out,
+                "#[test]\nfn {}() {{\n    \
+                 aoc_common::answers::assert_answers(&crate::registry(), {}, {}, {}, false);\n}}\n",
+                name, day, part, demo
+            )
+            .unwrap();
+        }
+
+        if let Some(reason) = &entry.ignore {
+            writeln!(
+                out,
+                "#[ignore = {:?}]\n#[test]\nfn {}_ignored() {{\n    \
+                 aoc_common::answers::assert_answers(&crate::registry(), {}, {}, {}, true);\n}}\n",
+                reason, name, day, part, demo
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+// Build script entry point writing the answer tests to `OUT_DIR/answer_tests.rs`.
+pub fn write_tests() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("Missing CARGO_MANIFEST_DIR");
+    let out_dir = std::env::var("OUT_DIR").expect("Missing OUT_DIR");
+
+    let manifest_dir = Path::new(&manifest_dir);
+    let answers_path = manifest_dir.join("answers.toml");
+
+    println!("cargo:rerun-if-changed={}", answers_path.display());
+
+    let answers = Answers::load(&answers_path).expect("Could not load answers.toml");
+    let tests = generate_tests(&answers);
+
+    fs::write(Path::new(&out_dir).join("answer_tests.rs"), tests)
+        .expect("Could not write answer tests");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solution;
+
+    const ANSWERS: &str = r#"
+[day04.part02.demo]
+answer = "4"
+input_hash = "0000000000000001"
+
+[day04.part02.real]
+answer = "801"
+input_hash = "0000000000000002"
+ignore = "slow"
+"#;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(answers.get(4, 2, true).unwrap().answer, "4");
+        assert_eq!(
+            answers.get(4, 2, false),
+            Some(&Entry {
+                answer: "801".to_string(),
+                input_hash: "0000000000000002".to_string(),
+                ignore: Some("slow".to_string()),
+                ignore_versions: None,
+            })
+        );
+        assert_eq!(answers.get(4, 1, false), None);
+
+        assert!(Answers::parse("[day4x.part01.real]\nanswer = \"1\"\ninput_hash = \"\"").is_err());
+        assert!(Answers::parse("[day04.part01.test]\nanswer = \"1\"\ninput_hash = \"\"").is_err());
+    }
+
+    #[test]
+    fn answers_round_trip() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+    }
+
+    #[test]
+    fn ignore_some_versions() {
+        let text = format!("{}ignore_versions = [1]\n", ANSWERS);
+        let answers = Answers::parse(&text).unwrap();
+        let entry = answers.get(4, 2, false).unwrap();
+
+        assert_eq!(entry.ignored(1), Some("slow"));
+        assert_eq!(entry.ignored(2), None);
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+
+        let entry = Answers::parse(ANSWERS).unwrap().get(4, 2, false).cloned();
+        assert_eq!(entry.unwrap().ignored(2), Some("slow"));
+    }
+
+    #[test]
+    fn expected_checks_input_hash() {
+        let mut answers = Answers::default();
+        answers.insert(
+            1,
+            1,
+            true,
+            Entry {
+                answer: "24000".to_string(),
+                input_hash: hash("1000\n2000\n"),
+                ignore: None,
+                ignore_versions: None,
+            },
+        );
+
+        assert_eq!(answers.expected(1, 1, true, "1000\n2000\n"), Some("24000"));
+        assert_eq!(answers.expected(1, 1, true, "1000\n"), None);
+        assert_eq!(answers.expected(1, 1, false, "1000\n2000\n"), None);
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(hash(""), "cbf29ce484222325");
+        assert_eq!(hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn generate_answer_tests() {
+        let text = format!("{}ignore_versions = [1]\n", ANSWERS);
+        let tests = generate_tests(&Answers::parse(&text).unwrap());
+        assert_eq!(
+            tests,
+            "#[test]\nfn day04_part02_demo() {\n    \
+             aoc_common::answers::assert_answers(&crate::registry(), 4, 2, true, false);\n}\n\n\
+             #[test]\nfn day04_part02_real() {\n    \
+             aoc_common::answers::assert_answers(&crate::registry(), 4, 2, false, false);\n}\n\n\
+             #[ignore = \"slow\"]\n#[test]\nfn day04_part02_real_ignored() {\n    \
+             aoc_common::answers::assert_answers(&crate::registry(), 4, 2, false, true);\n}\n\n"
+        );
+
+        // Without versions the whole entry is ignored
+        let tests = generate_tests(&Answers::parse(ANSWERS).unwrap());
+        assert!(!tests.contains("fn day04_part02_real()"), "{tests}");
+        assert!(tests.contains("fn day04_part02_real_ignored()"), "{tests}");
+    }
+
+    #[test]
+    fn answers_run_registered_versions() {
+        let dir = std::env::temp_dir().join(format!("aoc_{}_registered", std::process::id()));
+        let mut registry = Registry {
+            year: 2022,
+            src_dir: Box::leak(dir.to_string_lossy().to_string().into_boxed_str()),
+            solutions: vec![
+                Solution::new(2022, 1, 1, 1, |input| Ok(input.lines().count().to_string())),
+                Solution::new(2022, 1, 1, 2, |_| Ok("wrong".to_string())),
+            ],
+            answers: Answers::default(),
+            generators: vec![],
+        };
+        let input = "a\nb\n";
+        fs::create_dir_all(dir.join("day01")).unwrap();
+        fs::write(dir.join("day01/demo-input.txt"), input).unwrap();
+        registry.answers.insert(
+            1,
+            1,
+            true,
+            Entry {
+                answer: "2".to_string(),
+                input_hash: hash(input),
+                ignore: Some("wrong".to_string()),
+                ignore_versions: Some(vec![2]),
+            },
+        );
+
+        assert_answers(&registry, 1, 1, true, false);
+        let ignored = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            assert_answers(&registry, 1, 1, true, true)
+        }));
+        assert!(ignored.is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
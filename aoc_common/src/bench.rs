@@ -0,0 +1,234 @@
+// Statistical benchmark of the registered solutions. Each solution runs a few
+// warmup times, then many measured times, and the timings are summarized so
+// runs can be saved as a baseline and compared with later ones.
+//
+//   {
+//     "2023/day05_part01.real": {
+//       "iterations": 20,
+//       "min_ms": 0.281,
+//       ...
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::Memory;
+use crate::answers::input_kind;
+use crate::solution::Solution;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+// Summary of the measured timings in milliseconds
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub mean_ms: f64,
+    pub stddev_ms: f64,
+    // Heap usage of a run, only measured with the `alloc-stats` feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Memory>,
+}
+
+impl Stats {
+    pub fn new(timings: &[Duration]) -> Stats {
+        if timings.is_empty() {
+            return Stats::default();
+        }
+
+        let mut ms: Vec<f64> = timings.iter().map(|t| t.as_secs_f64() * 1000.0).collect();
+        ms.sort_by(f64::total_cmp);
+
+        let n = ms.len();
+        let median = match n % 2 {
+            0 => (ms[n / 2 - 1] + ms[n / 2]) / 2.0,
+            _ => ms[n / 2],
+        };
+        let mean = ms.iter().sum::<f64>() / n as f64;
+        // sample standard deviation, zero for a single timing
+        let variance = match n {
+            1 => 0.0,
+            _ => ms.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1) as f64,
+        };
+
+        Stats {
+            iterations: n,
+            min_ms: ms[0],
+            median_ms: median,
+            mean_ms: mean,
+            stddev_ms: variance.sqrt(),
+            memory: None,
+        }
+    }
+
+    // Change of the median from the baseline in percent, negative when faster
+    pub fn change(&self, baseline: &Stats) -> Option<f64> {
+        match baseline.median_ms > 0.0 {
+            true => Some((self.median_ms - baseline.median_ms) / baseline.median_ms * 100.0),
+            false => None,
+        }
+    }
+}
+
+// Runs the solution on the input, returning the stats or the first error.
+pub fn measure(solution: &Solution, input: &str, config: &Config) -> Result<Stats, String> {
+    for _ in 0..config.warmup {
+        solution.run(input).result?;
+    }
+
+    let mut timings = Vec::with_capacity(config.iterations);
+    let mut memory = None;
+    for _ in 0..config.iterations {
+        let run = solution.run(input);
+        run.result?;
+        timings.push(run.timing.total());
+        memory = memory.or(run.memory);
+    }
+
+    Ok(Stats {
+        memory,
+        ..Stats::new(&timings)
+    })
+}
+
+// Key of a solution input in the baseline, e.g. `2022/day02_part01_v2.real`
+pub fn key(solution: &Solution, demo: bool) -> String {
+    format!("{}/{}.{}", solution.year, solution.name(), input_kind(demo))
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baseline {
+    entries: BTreeMap<String, Stats>,
+}
+
+impl Baseline {
+    pub fn parse(text: &str) -> Result<Baseline, String> {
+        let entries = serde_json::from_str(text).map_err(|err| err.to_string())?;
+        Ok(Baseline { entries })
+    }
+
+    // Loads the baseline, an empty one when it was never saved.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Baseline, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Baseline::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Baseline::default()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut out =
+            serde_json::to_string_pretty(&self.entries).expect("Could not serialize baseline");
+        out.push('\n');
+        out
+    }
+
+    pub fn get(&self, solution: &Solution, demo: bool) -> Option<&Stats> {
+        self.entries.get(&key(solution, demo))
+    }
+
+    pub fn insert(&mut self, solution: &Solution, demo: bool, stats: Stats) {
+        self.entries.insert(key(solution, demo), stats);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn stats_summary() {
+        let timings: Vec<_> = [4, 1, 3, 2]
+            .iter()
+            .map(|ms| Duration::from_millis(*ms))
+            .collect();
+        let stats = Stats::new(&timings);
+
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.min_ms, 1.0);
+        assert_eq!(stats.median_ms, 2.5);
+        assert_eq!(stats.mean_ms, 2.5);
+        assert!((stats.stddev_ms - 1.2909944).abs() < 1e-6);
+
+        let stats = Stats::new(&[Duration::from_millis(5)]);
+        assert_eq!(stats.median_ms, 5.0);
+        assert_eq!(stats.stddev_ms, 0.0);
+
+        assert_eq!(Stats::new(&[]), Stats::default());
+    }
+
+    #[test]
+    fn stats_change() {
+        let baseline = Stats {
+            median_ms: 2.0,
+            ..Default::default()
+        };
+        let stats = Stats {
+            median_ms: 1.5,
+            ..Default::default()
+        };
+
+        assert_eq!(stats.change(&baseline), Some(-25.0));
+        assert_eq!(stats.change(&Stats::default()), None);
+    }
+
+    #[test]
+    fn measure_runs() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+        let solution = Solution::new(2022, 1, 1, 1, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok("".to_string())
+        });
+
+        let config = Config {
+            warmup: 2,
+            iterations: 5,
+        };
+        let stats = measure(&solution, "", &config).unwrap();
+        assert_eq!(stats.iterations, 5);
+        assert_eq!(runs.load(Ordering::SeqCst), 7);
+
+        let failing = Solution::new(2022, 1, 1, 1, |_| Err("boom".to_string()));
+        assert_eq!(measure(&failing, "", &config), Err("boom".to_string()));
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let v2 = Solution::new(2022, 2, 1, 2, |_| Ok("".to_string()));
+        let stats = Stats::new(&[Duration::from_millis(1), Duration::from_millis(3)]);
+
+        let mut baseline = Baseline::default();
+        baseline.insert(&v2, false, stats);
+
+        let baseline = Baseline::parse(&baseline.to_json()).unwrap();
+        assert_eq!(baseline.get(&v2, false), Some(&stats));
+        assert_eq!(baseline.get(&v2, true), None);
+        assert_eq!(baseline.len(), 1);
+        assert!(baseline
+            .to_json()
+            .starts_with("{\n  \"2022/day02_part01_v2.real\": {\n"));
+
+        assert!(Baseline::load("/nonexistent/baseline.json")
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,424 @@
+use clap::ValueEnum;
+use std::time::Duration;
+
+use crate::alloc::Memory;
+use crate::output::{Output, State};
+use crate::runner::{OverBudget, Record, Status};
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Tap,
+}
+
+// Size in bytes with a binary unit, e.g. `1.5 KiB`
+pub fn bytes(n: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if n < 1024 {
+        return format!("{} B", n);
+    }
+
+    let mut size = n as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+pub fn memory(memory: &Memory) -> String {
+    format!(
+        "peak {}, {} in {} allocations",
+        bytes(memory.peak_bytes),
+        bytes(memory.allocated_bytes),
+        memory.allocations
+    )
+}
+
+// Total time of a record, split into parse and solve when it is known, and
+// the heap usage when it was measured.
+fn timing(record: &Record) -> String {
+    let time = match record.parse_duration {
+        Some(parse) => format!(
+            "{:?}: parse {:?}, solve {:?}",
+            record.duration, parse, record.solve_duration
+        ),
+        None => format!("{:?}", record.duration),
+    };
+
+    let time = match &record.memory {
+        Some(m) => format!("{}; {}", time, memory(m)),
+        None => time,
+    };
+
+    let time = match over_budget(record) {
+        Some(over) => format!("{}; {}", time, over),
+        None => time,
+    };
+
+    match record.unreliable {
+        true => format!("{}; unreliable, a cancelled run was still going", time),
+        false => time,
+    }
+}
+
+// What budget of the solution the record went past
+pub fn over_budget(record: &Record) -> Option<String> {
+    let over = match record.over_budget? {
+        OverBudget::Time => format!("over the {:?} time budget", record.budget.time),
+        OverBudget::Memory => format!("over the {} memory ceiling", bytes(record.budget.memory)),
+        OverBudget::Timeout => format!("timed out after {:?}", record.budget.timeout()),
+    };
+
+    Some(over)
+}
+
+// Total wall time of the records of a year and the ones over their budget.
+pub fn summary(year: u16, records: &[Record], wall_time: Duration, output: &Output) -> String {
+    let runs = match records.len() {
+        1 => "1 run".to_string(),
+        n => format!("{} runs", n),
+    };
+    let mut out = format!("{}: {} in {:?} wall time\n", year, runs, wall_time);
+
+    let offenders: Vec<_> = records
+        .iter()
+        .filter_map(|r| Some((r, over_budget(r)?)))
+        .collect();
+
+    match offenders.len() {
+        0 => {}
+        1 => out.push_str("1 run over budget:\n"),
+        n => out.push_str(&format!("{} runs over budget:\n", n)),
+    }
+    for (r, over) in offenders {
+        out.push_str(&format!(
+            "{} day{:02} part{:02} v{} {}: {}\n",
+            output.icon(State::Failed),
+            r.day,
+            r.part,
+            r.version,
+            if r.demo { "demo" } else { "real" },
+            over
+        ));
+    }
+
+    match records.iter().filter(|r| r.unreliable).count() {
+        0 => {}
+        1 => out.push_str("1 run measured while a cancelled run was still going\n"),
+        n => out.push_str(&format!(
+            "{} runs measured while a cancelled run was still going\n",
+            n
+        )),
+    }
+
+    out
+}
+
+// Human readable line for a single record.
+pub fn text(record: &Record, output: &Output) -> String {
+    let icon = output.icon(State::from(record.status));
+    let answer = record.answer.as_deref().unwrap_or("");
+
+    match record.status {
+        Status::Passed => format!("{} Answer: {} ({})", icon, answer, timing(record)),
+        Status::Failed => format!(
+            "{} Answer: {} expected {} ({})",
+            icon,
+            answer,
+            record.expected.as_deref().unwrap_or(""),
+            timing(record)
+        ),
+        Status::Unknown => format!(
+            "{} Answer: {} no known answer to compare ({})",
+            icon,
+            answer,
+            timing(record)
+        ),
+        Status::Error => format!("{} Error: {}", icon, record.error.as_deref().unwrap_or("")),
+    }
+}
+
+// Renders all the records at once in one of the structured formats.
+pub fn render(format: Format, records: &[Record]) -> String {
+    match format {
+        Format::Text => records
+            .iter()
+            .map(|r| text(r, &Output::plain()) + "\n")
+            .collect(),
+        Format::Json => json(records),
+        Format::Csv => csv(records),
+        Format::Tap => tap(records),
+    }
+}
+
+fn json(records: &[Record]) -> String {
+    let mut out = serde_json::to_string_pretty(records).expect("Could not serialize records");
+    out.push('\n');
+    out
+}
+
+fn csv(records: &[Record]) -> String {
+    let mut out = String::from(
+        "year,day,part,version,demo,answer,expected,status,duration_ms,parse_ms,solve_ms,peak_bytes,allocated_bytes,allocations,over_budget,unreliable,crash,error\n",
+    );
+
+    for r in records {
+        let fields = [
+            r.year.to_string(),
+            r.day.to_string(),
+            r.part.to_string(),
+            r.version.to_string(),
+            r.demo.to_string(),
+            csv_field(r.answer.as_deref().unwrap_or("")),
+            csv_field(r.expected.as_deref().unwrap_or("")),
+            r.status.label().to_string(),
+            millis(r.duration),
+            r.parse_duration.map(millis).unwrap_or_default(),
+            millis(r.solve_duration),
+            optional(r.memory.map(|m| m.peak_bytes)),
+            optional(r.memory.map(|m| m.allocated_bytes)),
+            optional(r.memory.map(|m| m.allocations)),
+            r.over_budget
+                .map(|o| format!("{:?}", o).to_lowercase())
+                .unwrap_or_default(),
+            r.unreliable.to_string(),
+            r.crash
+                .as_ref()
+                .map(|c| c.kind())
+                .unwrap_or_default()
+                .to_string(),
+            csv_field(r.error.as_deref().unwrap_or("")),
+        ];
+
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+
+    out
+}
+
+fn optional(value: Option<usize>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+// Quotes a field only when it contains characters with special meaning in CSV.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn tap(records: &[Record]) -> String {
+    let mut out = format!("TAP version 13\n1..{}\n", records.len());
+
+    for (i, r) in records.iter().enumerate() {
+        let description = format!(
+            "{} day{:02} part{:02} v{} {}",
+            r.year,
+            r.day,
+            r.part,
+            r.version,
+            if r.demo { "demo" } else { "real" }
+        );
+        let answer = r.answer.as_deref().unwrap_or("");
+
+        let line = match r.status {
+            Status::Passed => format!("ok {} - {}: {}", i + 1, description, answer),
+            Status::Unknown => format!(
+                "ok {} - {}: {} # SKIP no known answer",
+                i + 1,
+                description,
+                answer
+            ),
+            Status::Failed => format!(
+                "not ok {} - {}: {}\n  # expected {}",
+                i + 1,
+                description,
+                answer,
+                r.expected.as_deref().unwrap_or("")
+            ),
+            Status::Error => format!(
+                "not ok {} - {}\n  # error {}",
+                i + 1,
+                description,
+                r.error.as_deref().unwrap_or("")
+            ),
+        };
+
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::Crash;
+    use crate::solution::Budget;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                year: 2022,
+                day: 4,
+                part: 2,
+                version: 1,
+                demo: false,
+                answer: Some("801".to_string()),
+                expected: Some("801".to_string()),
+                status: Status::Passed,
+                duration: Duration::from_micros(1500),
+                parse_duration: Some(Duration::from_micros(500)),
+                solve_duration: Duration::from_micros(1000),
+                memory: Some(Memory {
+                    peak_bytes: 2048,
+                    allocated_bytes: 3 << 20,
+                    allocations: 12,
+                }),
+                budget: Budget::default(),
+                over_budget: None,
+                unreliable: false,
+                crash: None,
+                error: None,
+            },
+            Record {
+                year: 2023,
+                day: 1,
+                part: 1,
+                version: 1,
+                demo: true,
+                answer: Some("1,2".to_string()),
+                expected: None,
+                status: Status::Unknown,
+                duration: Duration::from_micros(250),
+                parse_duration: None,
+                solve_duration: Duration::from_micros(250),
+                memory: None,
+                budget: Budget::default(),
+                over_budget: Some(OverBudget::Time),
+                unreliable: true,
+                crash: None,
+                error: None,
+            },
+            Record {
+                year: 2023,
+                day: 5,
+                part: 2,
+                version: 1,
+                demo: false,
+                answer: None,
+                expected: Some("46".to_string()),
+                status: Status::Error,
+                duration: Duration::ZERO,
+                parse_duration: None,
+                solve_duration: Duration::ZERO,
+                memory: None,
+                budget: Budget::default(),
+                over_budget: Some(OverBudget::Timeout),
+                unreliable: false,
+                crash: Some(Crash::Timeout),
+                error: Some("could not read input".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn render_json() {
+        let out = render(Format::Json, &records());
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        assert_eq!(value[0]["year"], 2022);
+        assert_eq!(value[0]["answer"], "801");
+        assert_eq!(value[0]["status"], "passed");
+        assert_eq!(value[0]["duration_ms"], 1.5);
+        assert_eq!(value[0]["parse_ms"], 0.5);
+        assert_eq!(value[0]["solve_ms"], 1.0);
+        assert_eq!(value[1]["parse_ms"], serde_json::Value::Null);
+        assert_eq!(value[0]["memory"]["peak_bytes"], 2048);
+        assert_eq!(value[1]["memory"], serde_json::Value::Null);
+        assert_eq!(value[1]["demo"], true);
+        assert_eq!(value[1]["expected"], serde_json::Value::Null);
+        assert_eq!(value[2]["status"], "error");
+        assert_eq!(value[0]["crash"], serde_json::Value::Null);
+        assert_eq!(value[2]["crash"]["kind"], "timeout");
+        assert_eq!(value[1]["unreliable"], true);
+    }
+
+    #[test]
+    fn render_csv() {
+        let out = render(Format::Csv, &records());
+        assert_eq!(
+            out,
+            "year,day,part,version,demo,answer,expected,status,duration_ms,parse_ms,solve_ms,peak_bytes,allocated_bytes,allocations,over_budget,unreliable,crash,error\n\
+             2022,4,2,1,false,801,801,passed,1.500,0.500,1.000,2048,3145728,12,,false,,\n\
+             2023,1,1,1,true,\"1,2\",,unknown,0.250,,0.250,,,,time,true,,\n\
+             2023,5,2,1,false,,46,error,0.000,,0.000,,,,timeout,false,timeout,could not read input\n"
+        );
+    }
+
+    #[test]
+    fn format_bytes() {
+        assert_eq!(bytes(0), "0 B");
+        assert_eq!(bytes(1023), "1023 B");
+        assert_eq!(bytes(1536), "1.5 KiB");
+        assert_eq!(bytes(5 << 30), "5.0 GiB");
+    }
+
+    #[test]
+    fn render_text() {
+        let records = records();
+        let output = Output::plain();
+
+        assert!(text(&records[0], &output)
+            .ends_with("(1.5ms: parse 500µs, solve 1ms; peak 2.0 KiB, 3.0 MiB in 12 allocations)"));
+        assert!(text(&records[1], &output).ends_with(
+            "(250µs; over the 1s time budget; unreliable, a cancelled run was still going)"
+        ));
+    }
+
+    #[test]
+    fn render_summary() {
+        let out = summary(
+            2023,
+            &records(),
+            Duration::from_millis(12),
+            &Output::plain(),
+        );
+
+        assert_eq!(
+            out,
+            "2023: 3 runs in 12ms wall time\n\
+             2 runs over budget:\n\
+             ✗ day01 part01 v1 demo: over the 1s time budget\n\
+             ✗ day05 part02 v1 real: timed out after 10s\n\
+             1 run measured while a cancelled run was still going\n"
+        );
+    }
+
+    #[test]
+    fn render_tap() {
+        let out = render(Format::Tap, &records());
+        assert_eq!(
+            out,
+            "TAP version 13\n\
+             1..3\n\
+             ok 1 - 2022 day04 part02 v1 real: 801\n\
+             ok 2 - 2023 day01 part01 v1 demo: 1,2 # SKIP no known answer\n\
+             not ok 3 - 2023 day05 part02 v1 real\n  # error could not read input\n"
+        );
+    }
+}
@@ -0,0 +1,238 @@
+// Built-in random fuzz driver for the parsers. Inputs from a corpus are
+// mutated and given to a target, which must never panic. Arithmetic overflows
+// panic in debug builds, so they are caught as well, and targets assert
+// their parsed output is bounded by the input size to catch unbounded
+// allocations. A crashing input is shrunk before being reported.
+//
+// The corpus of a target lives in `fuzz/corpus/<target>` of the year crate,
+// following the cargo-fuzz layout, and is seeded with the demo inputs.
+use std::env;
+use std::fmt;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+use crate::property;
+use crate::random::Rng;
+
+// Text known to be interesting for the parsers
+const TOKENS: [&str; 24] = [
+    "0",
+    "-1",
+    "4294967296",
+    "18446744073709551615",
+    "99999999999999999999",
+    " ",
+    "  ",
+    "\n",
+    "\n\n",
+    ":",
+    ",",
+    "-",
+    "|",
+    "[",
+    "]",
+    "*",
+    "é",
+    "😀",
+    "move",
+    "from",
+    "to",
+    "seeds:",
+    "map:",
+    "Card",
+];
+
+pub struct Config {
+    pub iterations: usize,
+    pub seed: u64,
+}
+
+impl Config {
+    // Runs 2000 iterations by default, `AOC_FUZZ_ITERATIONS` and
+    // `AOC_FUZZ_SEED` allow longer runs or other seeds.
+    pub fn new() -> Config {
+        let var = |name: &str| env::var(name).ok().and_then(|v| v.parse().ok());
+
+        Config {
+            iterations: var("AOC_FUZZ_ITERATIONS").unwrap_or(2000) as usize,
+            seed: var("AOC_FUZZ_SEED").unwrap_or(0),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config::new()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Crash {
+    pub input: String,
+    pub message: String,
+}
+
+impl fmt::Display for Crash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\nminimal input:\n{:?}", self.message, self.input)
+    }
+}
+
+// Every file of the corpus directory, sorted by name.
+pub fn corpus<P: AsRef<Path>>(dir: P) -> Vec<String> {
+    let mut files: Vec<_> = fs::read_dir(dir.as_ref())
+        .unwrap_or_else(|err| panic!("Could not read corpus {:?}: {}", dir.as_ref(), err))
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    files.sort();
+
+    files
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect()
+}
+
+pub fn mutate(rng: &mut Rng, input: &str, corpus: &[String]) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+
+    for _ in 0..rng.range(1, 4) {
+        let at = rng.index(chars.len() + 1);
+        let len = (rng.range(1, 8) as usize).min(chars.len() - at);
+
+        match rng.range(0, 5) {
+            0 => chars.insert(at, char::from(rng.range(0x20, 0x7e) as u8)),
+            1 => {
+                chars.drain(at..at + len);
+            }
+            2 if at < chars.len() => chars[at] = char::from(rng.range(0x20, 0x7e) as u8),
+            3 => {
+                let copy: Vec<char> = chars[at..at + len].to_vec();
+                chars.splice(at..at, copy);
+            }
+            4 => {
+                let token = rng.choose(&TOKENS);
+                chars.splice(at..at, token.chars());
+            }
+            _ => {
+                // Splices the end of another corpus input
+                let other: Vec<char> = rng.choose(corpus).chars().collect();
+                let from = rng.index(other.len() + 1);
+                chars.truncate(at);
+                chars.extend(&other[from..]);
+            }
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+fn crash<F: Fn(&str)>(input: &str, target: &F) -> Option<String> {
+    panic::catch_unwind(AssertUnwindSafe(|| target(input)))
+        .err()
+        .map(|payload| match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "panicked".to_string(),
+            },
+        })
+}
+
+// Runs the target on the corpus and on mutations of it.
+pub fn run<F: Fn(&str)>(config: &Config, corpus: &[String], target: F) -> Result<(), Crash> {
+    assert!(!corpus.is_empty(), "The corpus is empty");
+
+    let mut rng = Rng::new(config.seed);
+    let inputs = corpus
+        .iter()
+        .cloned()
+        .chain((0..config.iterations).map(|_| {
+            let input = rng.choose(corpus).clone();
+            mutate(&mut rng, &input, corpus)
+        }));
+
+    for input in inputs {
+        if crash(&input, &target).is_none() {
+            continue;
+        }
+
+        let input = property::shrink(&input, |i| crash(i, &target).is_some());
+        let message = crash(&input, &target).unwrap_or_default();
+
+        return Err(Crash { input, message });
+    }
+
+    Ok(())
+}
+
+// Fuzzes a target with its corpus from `fuzz/corpus/<name>` of the crate,
+// panicking with the minimal crashing input.
+pub fn assert_target<F: Fn(&str)>(crate_dir: &str, name: &str, target: F) {
+    let corpus = corpus(Path::new(crate_dir).join("fuzz/corpus").join(name));
+
+    if let Err(crash) = run(&Config::new(), &corpus, target) {
+        panic!("Fuzz target {} crashed: {}", name, crash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutate_is_deterministic() {
+        let corpus = vec!["move 1 from 2 to 1\n".to_string()];
+
+        let mut a = Rng::new(3);
+        let mut b = Rng::new(3);
+        for _ in 0..100 {
+            assert_eq!(
+                mutate(&mut a, &corpus[0], &corpus),
+                mutate(&mut b, &corpus[0], &corpus)
+            );
+        }
+
+        let mutated = mutate(&mut Rng::new(3), "", &corpus);
+        assert!(mutated.len() < 100);
+    }
+
+    #[test]
+    fn run_finds_crash() {
+        let config = Config {
+            iterations: 5000,
+            seed: 0,
+        };
+        let corpus = vec!["1 2 3\n".to_string()];
+
+        let target = |input: &str| {
+            for n in input.split_whitespace() {
+                let _: u8 = n.parse().unwrap_or(0);
+                if n == "0" {
+                    panic!("zero");
+                }
+            }
+        };
+
+        let crash = run(&config, &corpus, target).unwrap_err();
+        assert_eq!(crash.message, "zero");
+        assert_eq!(crash.input.trim(), "0");
+    }
+
+    #[test]
+    fn run_without_crash() {
+        let config = Config {
+            iterations: 500,
+            seed: 0,
+        };
+        let corpus = vec!["1 2 3\n".to_string()];
+
+        assert_eq!(
+            run(&config, &corpus, |input| {
+                input.split_whitespace().count();
+            }),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,676 @@
+// Graph searches over states given by a neighbor function instead of an
+// adjacency list, so the nodes can be grid positions, tuples or any other
+// hashable state, e.g.
+//
+//     let paths = graph::bfs(start, |&pos: &Pos| grid.neighbors4(pos));
+//     let steps = paths.cost(&goal);
+//
+// Unweighted searches take neighbor functions giving the next nodes,
+// weighted ones give the next nodes with the cost of moving to each of them.
+// Searches from a start return the Paths of every node they reached, the
+// `*_path` versions stop at the first goal found and return its path.
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+pub trait Node: Clone + Eq + Hash {}
+
+impl<N: Clone + Eq + Hash> Node for N {}
+
+// Costs of weighted graphs, with the default value as zero
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+pub trait Neighbors<N> {
+    type Iter: IntoIterator<Item = N>;
+
+    fn neighbors(&self, node: &N) -> Self::Iter;
+}
+
+impl<N, I, F> Neighbors<N> for F
+where
+    I: IntoIterator<Item = N>,
+    F: Fn(&N) -> I,
+{
+    type Iter = I;
+
+    fn neighbors(&self, node: &N) -> I {
+        self(node)
+    }
+}
+
+pub trait WeightedNeighbors<N, C> {
+    type Iter: IntoIterator<Item = (N, C)>;
+
+    fn neighbors(&self, node: &N) -> Self::Iter;
+}
+
+impl<N, C, I, F> WeightedNeighbors<N, C> for F
+where
+    I: IntoIterator<Item = (N, C)>,
+    F: Fn(&N) -> I,
+{
+    type Iter = I;
+
+    fn neighbors(&self, node: &N) -> I {
+        self(node)
+    }
+}
+
+// The nodes reached by a search with the cost of the path found to each of
+// them and the node before it on that path.
+#[derive(Clone, Debug)]
+pub struct Paths<N, C> {
+    reached: HashMap<N, (C, Option<N>)>,
+    order: Vec<N>,
+}
+
+impl<N: Node, C: Copy> Paths<N, C> {
+    fn new(start: N, cost: C) -> Paths<N, C> {
+        Paths {
+            reached: HashMap::from([(start, (cost, None))]),
+            order: vec![],
+        }
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.reached.get(node).map(|(cost, _)| *cost)
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.reached.contains_key(node)
+    }
+
+    // Nodes from the start to the node, both included
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut current = &self.reached.get(node)?.1;
+
+        while let Some(previous) = current {
+            path.push(previous.clone());
+            current = &self.reached[previous].1;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    // Nodes in the order the search visited them
+    pub fn order(&self) -> &[N] {
+        &self.order
+    }
+
+    pub fn len(&self) -> usize {
+        self.reached.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reached.is_empty()
+    }
+
+    fn into_path(self, goal: Option<N>) -> Option<Path<N, C>> {
+        let goal = goal?;
+
+        Some(Path {
+            cost: self.cost(&goal)?,
+            nodes: self.path(&goal)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+// Breadth-first search, the cost of a node being its number of steps from
+// the start.
+pub fn bfs<N: Node, G: Neighbors<N>>(start: N, graph: G) -> Paths<N, usize> {
+    breadth_first(start, &graph, |_| false).0
+}
+
+pub fn bfs_path<N, G, F>(start: N, graph: G, is_goal: F) -> Option<Path<N, usize>>
+where
+    N: Node,
+    G: Neighbors<N>,
+    F: FnMut(&N) -> bool,
+{
+    let (paths, goal) = breadth_first(start, &graph, is_goal);
+    paths.into_path(goal)
+}
+
+fn breadth_first<N, G, F>(start: N, graph: &G, mut is_goal: F) -> (Paths<N, usize>, Option<N>)
+where
+    N: Node,
+    G: Neighbors<N>,
+    F: FnMut(&N) -> bool,
+{
+    let mut paths = Paths::new(start.clone(), 0);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        paths.order.push(node.clone());
+        if is_goal(&node) {
+            return (paths, Some(node));
+        }
+
+        for next in graph.neighbors(&node) {
+            if let Entry::Vacant(entry) = paths.reached.entry(next.clone()) {
+                entry.insert((steps + 1, Some(node.clone())));
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    (paths, None)
+}
+
+// Depth-first search, visiting the neighbors of a node in their order before
+// its next sibling. The cost of a node is its depth in the search tree, not
+// its distance from the start.
+pub fn dfs<N: Node, G: Neighbors<N>>(start: N, graph: G) -> Paths<N, usize> {
+    let mut paths = Paths::new(start.clone(), 0);
+    paths.order.push(start.clone());
+    let mut stack = vec![(start.clone(), 0, graph.neighbors(&start).into_iter())];
+
+    while let Some((node, depth, neighbors)) = stack.last_mut() {
+        let Some(next) = neighbors.next() else {
+            stack.pop();
+            continue;
+        };
+
+        if let Entry::Vacant(entry) = paths.reached.entry(next.clone()) {
+            let depth = *depth + 1;
+            entry.insert((depth, Some(node.clone())));
+            paths.order.push(next.clone());
+
+            let neighbors = graph.neighbors(&next).into_iter();
+            stack.push((next, depth, neighbors));
+        }
+    }
+
+    paths
+}
+
+// Cheapest paths from the start for non-negative costs. The order of the
+// paths is the order of increasing cost.
+pub fn dijkstra<N, C, G>(start: N, graph: G) -> Paths<N, C>
+where
+    N: Node,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+{
+    best_first(start, &graph, |_| C::default(), |_| false).0
+}
+
+pub fn dijkstra_path<N, C, G, F>(start: N, graph: G, is_goal: F) -> Option<Path<N, C>>
+where
+    N: Node,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+    F: FnMut(&N) -> bool,
+{
+    let (paths, goal) = best_first(start, &graph, |_| C::default(), is_goal);
+    paths.into_path(goal)
+}
+
+// Cheapest path from the start to a goal, exploring first the nodes with the
+// lowest cost plus heuristic. The path is the cheapest one as long as the
+// heuristic never overestimates the cost left to a goal.
+pub fn astar<N, C, G, H, F>(start: N, graph: G, heuristic: H, is_goal: F) -> Option<Path<N, C>>
+where
+    N: Node,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+    H: Fn(&N) -> C,
+    F: FnMut(&N) -> bool,
+{
+    let (paths, goal) = best_first(start, &graph, heuristic, is_goal);
+    paths.into_path(goal)
+}
+
+fn best_first<N, C, G, H, F>(
+    start: N,
+    graph: &G,
+    heuristic: H,
+    mut is_goal: F,
+) -> (Paths<N, C>, Option<N>)
+where
+    N: Node,
+    C: Cost,
+    G: WeightedNeighbors<N, C>,
+    H: Fn(&N) -> C,
+    F: FnMut(&N) -> bool,
+{
+    let mut paths = Paths::new(start.clone(), C::default());
+    // Nodes are not required to be ordered, the heap refers to them by index
+    let mut queued = vec![start.clone()];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let node = queued[index].clone();
+        // A cheaper path to the node was found after this one was queued
+        if paths.cost(&node).is_some_and(|best| cost > best) {
+            continue;
+        }
+
+        paths.order.push(node.clone());
+        if is_goal(&node) {
+            return (paths, Some(node));
+        }
+
+        for (next, step) in graph.neighbors(&node) {
+            let next_cost = cost + step;
+            if paths.cost(&next).is_some_and(|best| best <= next_cost) {
+                continue;
+            }
+
+            paths
+                .reached
+                .insert(next.clone(), (next_cost, Some(node.clone())));
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                queued.len(),
+            )));
+            queued.push(next);
+        }
+    }
+
+    (paths, None)
+}
+
+// Nodes found in a cycle, each one leading to the next and the last one back
+// to the first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CycleError<N> {
+    pub cycle: Vec<N>,
+}
+
+// Orders the nodes reachable from the given ones so every node comes before
+// its neighbors, failing when they are part of a cycle.
+pub fn topological_sort<N, G, I>(nodes: I, graph: G) -> Result<Vec<N>, CycleError<N>>
+where
+    N: Node,
+    G: Neighbors<N>,
+    I: IntoIterator<Item = N>,
+{
+    // Nodes still on the stack are open, the others are done
+    let mut open: HashMap<N, bool> = HashMap::new();
+    let mut sorted = vec![];
+
+    for root in nodes {
+        if open.contains_key(&root) {
+            continue;
+        }
+
+        open.insert(root.clone(), true);
+        let mut stack = vec![(root.clone(), graph.neighbors(&root).into_iter())];
+
+        while let Some((node, neighbors)) = stack.last_mut() {
+            let Some(next) = neighbors.next() else {
+                open.insert(node.clone(), false);
+                sorted.push(node.clone());
+                stack.pop();
+                continue;
+            };
+
+            match open.get(&next) {
+                Some(true) => {
+                    let start = stack.iter().position(|(n, _)| *n == next).unwrap_or(0);
+                    let cycle = stack[start..].iter().map(|(n, _)| n.clone()).collect();
+                    return Err(CycleError { cycle });
+                }
+                Some(false) => {}
+                None => {
+                    open.insert(next.clone(), true);
+                    let neighbors = graph.neighbors(&next).into_iter();
+                    stack.push((next, neighbors));
+                }
+            }
+        }
+    }
+
+    sorted.reverse();
+    Ok(sorted)
+}
+
+// Strongly connected components of the nodes reachable from the given ones,
+// with Tarjan's algorithm. A component only comes after the components it
+// leads to, so the list is in reverse topological order.
+pub fn strongly_connected_components<N, G, I>(nodes: I, graph: G) -> Vec<Vec<N>>
+where
+    N: Node,
+    G: Neighbors<N>,
+    I: IntoIterator<Item = N>,
+{
+    let mut tarjan = Tarjan {
+        graph,
+        index: HashMap::new(),
+        nodes: vec![],
+        low: vec![],
+        on_stack: vec![],
+        stack: vec![],
+        calls: vec![],
+        components: vec![],
+    };
+
+    for root in nodes {
+        if !tarjan.index.contains_key(&root) {
+            tarjan.visit(root);
+            tarjan.run();
+        }
+    }
+
+    tarjan.components
+}
+
+type NeighborsIter<N, G> = <<G as Neighbors<N>>::Iter as IntoIterator>::IntoIter;
+
+struct Tarjan<N, G: Neighbors<N>> {
+    graph: G,
+    // Nodes are numbered in the order they are visited
+    index: HashMap<N, usize>,
+    nodes: Vec<N>,
+    // Lowest number of the nodes reachable from each node still on the stack
+    low: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    // Nodes being visited with their neighbors left to visit
+    calls: Vec<(usize, NeighborsIter<N, G>)>,
+    components: Vec<Vec<N>>,
+}
+
+impl<N: Node, G: Neighbors<N>> Tarjan<N, G> {
+    fn visit(&mut self, node: N) {
+        let id = self.nodes.len();
+        self.index.insert(node.clone(), id);
+        self.low.push(id);
+        self.on_stack.push(true);
+        self.stack.push(id);
+        self.calls
+            .push((id, self.graph.neighbors(&node).into_iter()));
+        self.nodes.push(node);
+    }
+
+    fn run(&mut self) {
+        while let Some((v, neighbors)) = self.calls.last_mut() {
+            let v = *v;
+
+            if let Some(next) = neighbors.next() {
+                match self.index.get(&next) {
+                    Some(&w) if self.on_stack[w] => self.low[v] = self.low[v].min(w),
+                    Some(_) => {}
+                    None => self.visit(next),
+                }
+                continue;
+            }
+
+            self.calls.pop();
+            if let Some(&(u, _)) = self.calls.last() {
+                self.low[u] = self.low[u].min(self.low[v]);
+            }
+
+            if self.low[v] == v {
+                let mut component = vec![];
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack[w] = false;
+                    component.push(self.nodes[w].clone());
+                    if w == v {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Pos};
+    use crate::random::Rng;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.####.#.
+......#E";
+
+    fn open_neighbors(grid: &Grid<char>) -> impl Fn(&Pos) -> Vec<Pos> + '_ {
+        |&pos| grid.neighbors4(pos).filter(|&n| grid[n] != '#').collect()
+    }
+
+    fn find(grid: &Grid<char>, c: char) -> Pos {
+        grid.iter().find(|(_, &cell)| cell == c).unwrap().0
+    }
+
+    // Adjacency lists of a random directed graph over `nodes` nodes
+    fn random_graph(rng: &mut Rng, nodes: usize, edges: usize) -> Vec<Vec<(usize, u32)>> {
+        let mut graph = vec![vec![]; nodes];
+        for _ in 0..edges {
+            let from = rng.index(nodes);
+            graph[from].push((rng.index(nodes), rng.range(0, 9) as u32));
+        }
+        graph
+    }
+
+    #[test]
+    fn bfs_shortest_steps() {
+        let grid = Grid::from_chars(MAZE).unwrap();
+        let (start, end) = (find(&grid, 'S'), find(&grid, 'E'));
+
+        let paths = bfs(start, open_neighbors(&grid));
+        assert_eq!(paths.cost(&end), Some(15));
+        assert_eq!(paths.cost(&Pos::new(0, 3)), Some(7));
+        assert_eq!(paths.cost(&Pos::new(0, 2)), None);
+        assert_eq!(paths.order()[0], start);
+        assert_eq!(paths.len(), grid.iter().filter(|(_, &c)| c != '#').count());
+
+        let path = bfs_path(start, open_neighbors(&grid), |&pos| pos == end).unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.nodes.len(), 16);
+        assert_eq!((path.nodes[0], path.nodes[15]), (start, end));
+        for step in path.nodes.windows(2) {
+            assert!(grid.neighbors4(step[0]).any(|n| n == step[1]));
+        }
+
+        let walled = |&pos: &Pos| {
+            grid.neighbors4(pos)
+                .filter(|&n| grid[n] == 'x')
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(bfs_path(start, walled, |&pos| pos == end), None);
+    }
+
+    #[test]
+    fn dfs_visits_depth_first() {
+        let graph = [vec![1, 2], vec![3], vec![3], vec![0]];
+        let paths = dfs(0, |&n: &usize| graph[n].clone());
+
+        assert_eq!(paths.order(), &[0, 1, 3, 2]);
+        assert_eq!(paths.path(&3), Some(vec![0, 1, 3]));
+        assert_eq!(paths.cost(&2), Some(1));
+        assert_eq!(paths.cost(&3), Some(2));
+    }
+
+    #[test]
+    fn dijkstra_cheapest_paths() {
+        // 0 -> 1 costs 7 directly but 3 through 2
+        let graph = [
+            vec![(1, 7), (2, 1)],
+            vec![(3, 1)],
+            vec![(1, 2), (3, 9)],
+            vec![],
+        ];
+        let paths = dijkstra(0, |&n: &usize| graph[n].clone());
+
+        assert_eq!(paths.cost(&1), Some(3));
+        assert_eq!(paths.cost(&3), Some(4));
+        assert_eq!(paths.path(&3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(paths.order(), &[0, 2, 1, 3]);
+
+        let path = dijkstra_path(0, |&n: &usize| graph[n].clone(), |&n| n == 1).unwrap();
+        assert_eq!(
+            path,
+            Path {
+                nodes: vec![0, 2, 1],
+                cost: 3,
+            }
+        );
+        assert_eq!(
+            dijkstra_path(3, |&n: &usize| graph[n].clone(), |&n| n == 0),
+            None
+        );
+    }
+
+    #[test]
+    fn astar_on_tuple_states() {
+        // Reaching (x, y) with steps right or down costing their distance
+        // from the diagonal
+        let goal = (20i32, 30i32);
+        let moves = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| x <= goal.0 && y <= goal.1)
+                .map(|(x, y)| ((x, y), 1 + (x - y).unsigned_abs()))
+                .collect::<Vec<_>>()
+        };
+        let remaining = |&(x, y): &(i32, i32)| ((goal.0 - x) + (goal.1 - y)) as u32;
+
+        let path = astar((0, 0), moves, remaining, |&n| n == goal).unwrap();
+        let cheapest = dijkstra((0, 0), moves).cost(&goal);
+
+        assert_eq!(Some(path.cost), cheapest);
+        assert_eq!(path.nodes.len(), 51);
+    }
+
+    #[test]
+    fn searches_agree_on_random_graphs() {
+        let mut rng = Rng::new(3);
+
+        for _ in 0..200 {
+            let nodes = rng.range(1, 30) as usize;
+            let graph = random_graph(&mut rng, nodes, nodes * 3);
+            let weighted = |&n: &usize| graph[n].clone();
+            let unit = |&n: &usize| graph[n].iter().map(|&(m, _)| (m, 1)).collect::<Vec<_>>();
+            let plain = |&n: &usize| graph[n].iter().map(|&(m, _)| m).collect::<Vec<_>>();
+            let goal = rng.index(nodes);
+
+            // Unit costs make Dijkstra count steps like BFS
+            let steps = bfs(0, plain);
+            let unit_costs = dijkstra(0, unit);
+            let depths = dfs(0, plain);
+            assert_eq!(steps.len(), unit_costs.len());
+            assert_eq!(steps.len(), depths.len());
+            for node in 0..nodes {
+                assert_eq!(steps.cost(&node), unit_costs.cost(&node));
+            }
+
+            // A zero heuristic makes A* a Dijkstra search
+            let costs = dijkstra(0, weighted);
+            let path = astar(0, weighted, |_| 0, |&n| n == goal);
+            assert_eq!(path.as_ref().map(|p| p.cost), costs.cost(&goal));
+
+            if let Some(path) = path {
+                let total: u32 = path
+                    .nodes
+                    .windows(2)
+                    .map(|w| {
+                        graph[w[0]]
+                            .iter()
+                            .filter(|e| e.0 == w[1])
+                            .map(|e| e.1)
+                            .min()
+                            .unwrap()
+                    })
+                    .sum();
+                assert_eq!(total, path.cost);
+            }
+        }
+    }
+
+    #[test]
+    fn topological_order() {
+        // Each node must be done before the ones it leads to
+        let graph = [vec![], vec![0, 3], vec![1], vec![0], vec![2, 3]];
+        let sorted = topological_sort(0..5, |&n: &usize| graph[n].clone()).unwrap();
+
+        assert_eq!(sorted.len(), 5);
+        let position = |n: usize| sorted.iter().position(|&m| m == n).unwrap();
+        for (from, nexts) in graph.iter().enumerate() {
+            for &to in nexts {
+                assert!(position(from) < position(to), "{sorted:?}");
+            }
+        }
+
+        assert_eq!(
+            topological_sort(["a"], |&n: &&str| match n {
+                "a" => vec!["b"],
+                _ => vec![],
+            }),
+            Ok(vec!["a", "b"])
+        );
+    }
+
+    #[test]
+    fn topological_sort_finds_cycles() {
+        let graph = [vec![1], vec![2], vec![3], vec![1]];
+        let err = topological_sort([0], |&n: &usize| graph[n].clone()).unwrap_err();
+        assert_eq!(err.cycle, vec![1, 2, 3]);
+
+        let err = topological_sort([0], |_: &usize| vec![0]).unwrap_err();
+        assert_eq!(err.cycle, vec![0]);
+    }
+
+    #[test]
+    fn components_of_a_graph() {
+        // {0, 1, 2} is a cycle leading to the cycle {3, 4}, 5 is on its own
+        let graph = [vec![1], vec![2], vec![0, 3], vec![4], vec![3], vec![4]];
+        let mut components = strongly_connected_components(0..6, |&n: &usize| graph[n].clone());
+        for component in components.iter_mut() {
+            component.sort();
+        }
+
+        assert_eq!(components, vec![vec![3, 4], vec![0, 1, 2], vec![5]]);
+    }
+
+    #[test]
+    fn components_match_reachability() {
+        let mut rng = Rng::new(11);
+
+        for _ in 0..100 {
+            let nodes = rng.range(1, 20) as usize;
+            let graph = random_graph(&mut rng, nodes, nodes * 2);
+            let plain = |&n: &usize| graph[n].iter().map(|&(m, _)| m).collect::<Vec<_>>();
+            let reach: Vec<Paths<usize, usize>> = (0..nodes).map(|n| bfs(n, plain)).collect();
+
+            let components = strongly_connected_components(0..nodes, plain);
+            assert_eq!(components.iter().map(|c| c.len()).sum::<usize>(), nodes);
+
+            let mut component_of = vec![0; nodes];
+            for (i, component) in components.iter().enumerate() {
+                for &n in component {
+                    component_of[n] = i;
+                }
+            }
+            for a in 0..nodes {
+                for b in 0..nodes {
+                    let same = reach[a].contains(&b) && reach[b].contains(&a);
+                    assert_eq!(same, component_of[a] == component_of[b]);
+
+                    // Components only lead to the ones before them
+                    if reach[a].contains(&b) {
+                        assert!(component_of[b] <= component_of[a]);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,315 @@
+// Rectangular grid of cells as found in many puzzle inputs, one row per line
+// and one cell per character:
+//
+//   467..114..
+//   ...*......
+//
+// Positions are `(row, col)` from the top left cell. Every access is bounds
+// checked, so neighbors past the edges are simply left out.
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Pos {
+        Pos { row, col }
+    }
+}
+
+// Offsets of the 4 orthogonal neighbors, then the 4 diagonal ones
+const OFFSETS: [(isize, isize); 8] = [
+    (-1, 0),
+    (0, -1),
+    (0, 1),
+    (1, 0),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl Grid<char> {
+    pub fn from_chars(input: &str) -> Result<Grid<char>, String> {
+        Grid::parse(input, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    // Parses every character of the input lines into a cell. All the lines
+    // must be as long as the first one.
+    pub fn parse<F: FnMut(char) -> T>(input: &str, mut cell: F) -> Result<Grid<T>, String> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (row, line) in input.lines().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+
+            let row_width = cells.len() - len;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(format!(
+                        "row {} has {} cells instead of {}",
+                        row, row_width, width
+                    ))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        match self.contains(pos) {
+            true => self.cells.get(pos.row * self.width + pos.col),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        match self.contains(pos) {
+            true => self.cells.get_mut(pos.row * self.width + pos.col),
+            false => None,
+        }
+    }
+
+    // Position moved by the offset, None when it falls off the grid
+    pub fn offset(&self, pos: Pos, rows: isize, cols: isize) -> Option<Pos> {
+        let row = pos.row.checked_add_signed(rows)?;
+        let col = pos.col.checked_add_signed(cols)?;
+        let pos = Pos::new(row, col);
+
+        self.contains(pos).then_some(pos)
+    }
+
+    // Cells above, left, right and below the position
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS[..4]
+            .iter()
+            .filter_map(move |(rows, cols)| self.offset(pos, *rows, *cols))
+    }
+
+    // Orthogonal and diagonal neighbors of the position
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS
+            .iter()
+            .filter_map(move |(rows, cols)| self.offset(pos, *rows, *cols))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        match row < self.height {
+            true => Some(&self.cells[row * self.width..(row + 1) * self.width]),
+            false => None,
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).filter_map(|row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> Option<impl Iterator<Item = &T>> {
+        match col < self.width {
+            true => Some(self.cells.iter().skip(col).step_by(self.width)),
+            false => None,
+        }
+    }
+
+    // All the positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Pos::new(row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Positions connected to the start through orthogonal neighbors matching
+    // the predicate, in the order they are reached. Empty when the start
+    // itself does not match.
+    pub fn region<F: Fn(&T) -> bool>(&self, start: Pos, matches: F) -> Vec<Pos> {
+        let mut seen = Grid::new(self.width, self.height, false);
+        self.fill(start, &matches, &mut seen)
+    }
+
+    // Every region of cells matching the predicate, ordered by their first
+    // position.
+    pub fn regions<F: Fn(&T) -> bool>(&self, matches: F) -> Vec<Vec<Pos>> {
+        let mut seen = Grid::new(self.width, self.height, false);
+
+        self.positions()
+            .map(|pos| self.fill(pos, &matches, &mut seen))
+            .filter(|region| !region.is_empty())
+            .collect()
+    }
+
+    fn fill<F: Fn(&T) -> bool>(&self, start: Pos, matches: &F, seen: &mut Grid<bool>) -> Vec<Pos> {
+        let mut region = vec![];
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(pos) = queue.pop_front() {
+            match (self.get(pos), seen.get_mut(pos)) {
+                (Some(cell), Some(seen)) if !*seen && matches(cell) => *seen = true,
+                _ => continue,
+            }
+
+            region.push(pos);
+            queue.extend(self.neighbors4(pos));
+        }
+
+        region
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+// Prints the cells row by row, as they were parsed for a grid of chars
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "#..\n.##\n..#\n";
+
+    #[test]
+    fn parse_and_print() {
+        let grid = Grid::from_chars(INPUT).unwrap();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[Pos::new(1, 2)], '#');
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.get(Pos::new(0, 3)), None);
+        assert_eq!(grid.to_string(), INPUT);
+
+        assert_eq!(
+            Grid::from_chars("...\n..\n"),
+            Err("row 1 has 2 cells instead of 3".to_string())
+        );
+        assert_eq!(Grid::from_chars("").unwrap().positions().count(), 0);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::from_chars(INPUT).unwrap();
+
+        let corner: Vec<_> = grid.neighbors4(Pos::new(0, 0)).collect();
+        assert_eq!(corner, vec![Pos::new(0, 1), Pos::new(1, 0)]);
+
+        assert_eq!(grid.neighbors8(Pos::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(Pos::new(2, 1)).count(), 5);
+        assert_eq!(grid.neighbors4(Pos::new(1, 1)).count(), 4);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::parse("123\n456\n", |c| c.to_digit(10).unwrap()).unwrap();
+
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(2).unwrap().collect::<Vec<_>>(), vec![&3, &6]);
+        assert!(grid.column(3).is_none());
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.iter().nth(4), Some((Pos::new(1, 1), &5)));
+        assert_eq!(grid.map(|n| n * 2).row(0), Some(&[2, 4, 6][..]));
+
+        let empty = Grid::<u32>::parse("", |_| 0).unwrap();
+        assert_eq!(empty.row(0), None);
+        assert!(empty.column(0).is_none());
+    }
+
+    #[test]
+    fn regions() {
+        let grid = Grid::from_chars(INPUT).unwrap();
+
+        assert_eq!(
+            grid.regions(|c| *c == '#'),
+            vec![
+                vec![Pos::new(0, 0)],
+                vec![Pos::new(1, 1), Pos::new(1, 2), Pos::new(2, 2)]
+            ]
+        );
+        assert_eq!(grid.region(Pos::new(0, 1), |c| *c == '.').len(), 2);
+        assert!(grid.region(Pos::new(0, 0), |c| *c == '.').is_empty());
+    }
+}
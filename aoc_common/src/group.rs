@@ -0,0 +1,314 @@
+// Groups the lines of an input into records, for both the streamed
+// `io::Lines` of 2022 and the `str::lines` of 2023:
+//
+// * `paragraphs` gives the runs of lines separated by blank lines.
+// * `sections` gives the same runs split into their first line, the header,
+//   and the lines after it, e.g. `seed-to-soil map:` and its rows.
+// * `chunks` gives every `size` lines, failing on a last partial chunk.
+//
+// Blank lines are the ones with only whitespace, and repeated or trailing
+// blank lines never give an empty paragraph. Lines that can fail to be read,
+// like the `io::Result<String>` of `io::Lines`, give groups that can fail the
+// same way, so `str` lines give `Vec<&str>` and `io::Lines` gives
+// `io::Result<Vec<String>>`.
+use std::convert::Infallible;
+use std::fmt;
+
+// A line of an input, read or still to be read
+pub trait Line {
+    type Text: AsRef<str>;
+    type Error;
+    // The group of lines of this kind, e.g. `G` or `Result<G, Self::Error>`
+    type Wrap<G>;
+
+    fn text(self) -> Result<Self::Text, Self::Error>;
+
+    fn wrap<G>(group: Result<G, Self::Error>) -> Self::Wrap<G>;
+}
+
+impl<'a> Line for &'a str {
+    type Text = &'a str;
+    type Error = Infallible;
+    type Wrap<G> = G;
+
+    fn text(self) -> Result<&'a str, Infallible> {
+        Ok(self)
+    }
+
+    fn wrap<G>(group: Result<G, Infallible>) -> G {
+        match group {
+            Ok(group) => group,
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl Line for String {
+    type Text = String;
+    type Error = Infallible;
+    type Wrap<G> = G;
+
+    fn text(self) -> Result<String, Infallible> {
+        Ok(self)
+    }
+
+    fn wrap<G>(group: Result<G, Infallible>) -> G {
+        <&str as Line>::wrap(group)
+    }
+}
+
+impl<T: AsRef<str>, E> Line for Result<T, E> {
+    type Text = T;
+    type Error = E;
+    type Wrap<G> = Result<G, E>;
+
+    fn text(self) -> Result<T, E> {
+        self
+    }
+
+    fn wrap<G>(group: Result<G, E>) -> Result<G, E> {
+        group
+    }
+}
+
+type Text<I> = <<I as Iterator>::Item as Line>::Text;
+type Error<I> = <<I as Iterator>::Item as Line>::Error;
+type Wrap<I, G> = <<I as Iterator>::Item as Line>::Wrap<G>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GroupError<E> {
+    Line(E),
+    // The last chunk has only `len` of the `size` lines
+    PartialChunk { size: usize, len: usize },
+}
+
+impl<E: fmt::Display> fmt::Display for GroupError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GroupError::Line(err) => write!(f, "could not read line: {err}"),
+            GroupError::PartialChunk { size, len } => {
+                write!(f, "the last chunk has {len} of {size} lines")
+            }
+        }
+    }
+}
+
+// A paragraph split into its first line and the rest
+#[derive(Clone, Debug, PartialEq)]
+pub struct Section<T> {
+    pub header: T,
+    pub body: Vec<T>,
+}
+
+pub trait Grouping: Iterator + Sized
+where
+    Self::Item: Line,
+{
+    fn paragraphs(self) -> Paragraphs<Self> {
+        Paragraphs { lines: self }
+    }
+
+    fn sections(self) -> Sections<Self> {
+        Sections { lines: self }
+    }
+
+    fn chunks(self, size: usize) -> Chunks<Self> {
+        assert!(size > 0, "chunks need at least one line");
+        Chunks { lines: self, size }
+    }
+}
+
+impl<I: Iterator> Grouping for I where I::Item: Line {}
+
+// The next lines up to a blank line, skipping the blank lines before them
+fn next_paragraph<I>(lines: &mut I) -> Option<Result<Vec<Text<I>>, Error<I>>>
+where
+    I: Iterator,
+    I::Item: Line,
+{
+    let mut paragraph = vec![];
+
+    for line in lines {
+        match line.text() {
+            Err(err) => return Some(Err(err)),
+            Ok(text) if text.as_ref().trim().is_empty() => {
+                if !paragraph.is_empty() {
+                    break;
+                }
+            }
+            Ok(text) => paragraph.push(text),
+        }
+    }
+
+    (!paragraph.is_empty()).then_some(Ok(paragraph))
+}
+
+pub struct Paragraphs<I> {
+    lines: I,
+}
+
+impl<I: Iterator> Iterator for Paragraphs<I>
+where
+    I::Item: Line,
+{
+    type Item = Wrap<I, Vec<Text<I>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_paragraph(&mut self.lines).map(I::Item::wrap)
+    }
+}
+
+pub struct Sections<I> {
+    lines: I,
+}
+
+impl<I: Iterator> Iterator for Sections<I>
+where
+    I::Item: Line,
+{
+    type Item = Wrap<I, Section<Text<I>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let paragraph = next_paragraph(&mut self.lines)?;
+
+        let section = paragraph.map(|lines| {
+            let mut lines = lines.into_iter();
+            Section {
+                header: lines.next().expect("paragraphs are never empty"),
+                body: lines.collect(),
+            }
+        });
+        Some(I::Item::wrap(section))
+    }
+}
+
+pub struct Chunks<I> {
+    lines: I,
+    size: usize,
+}
+
+impl<I: Iterator> Iterator for Chunks<I>
+where
+    I::Item: Line,
+{
+    type Item = Result<Vec<Text<I>>, GroupError<Error<I>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.size);
+
+        for line in self.lines.by_ref().take(self.size) {
+            match line.text() {
+                Ok(text) => chunk.push(text),
+                Err(err) => return Some(Err(GroupError::Line(err))),
+            }
+        }
+
+        match chunk.len() {
+            0 => None,
+            len if len < self.size => Some(Err(GroupError::PartialChunk {
+                size: self.size,
+                len,
+            })),
+            _ => Some(Ok(chunk)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{self, BufRead, Cursor};
+
+    fn io_lines(input: &[u8]) -> io::Lines<Cursor<Vec<u8>>> {
+        Cursor::new(input.to_vec()).lines()
+    }
+
+    #[test]
+    fn paragraphs_of_str_lines() {
+        let input = "\n1000\n2000\n\n\n4000\n  \n5000\n6000\n\n";
+        let paragraphs: Vec<Vec<&str>> = input.lines().paragraphs().collect();
+
+        assert_eq!(
+            paragraphs,
+            vec![vec!["1000", "2000"], vec!["4000"], vec!["5000", "6000"]]
+        );
+        assert_eq!("".lines().paragraphs().count(), 0);
+        assert_eq!("\n \n".lines().paragraphs().count(), 0);
+    }
+
+    #[test]
+    fn paragraphs_of_io_lines() {
+        let paragraphs: Vec<Vec<String>> = io_lines(b"a\nb\n\nc")
+            .paragraphs()
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(paragraphs, vec![vec!["a", "b"], vec!["c"]]);
+
+        // Invalid UTF-8 fails to be read as a String
+        let mut paragraphs = io_lines(b"a\n\n\xff\nb").paragraphs();
+        assert_eq!(paragraphs.next().unwrap().unwrap(), vec!["a"]);
+        let err = paragraphs.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn sections_split_headers() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n";
+        let sections: Vec<Section<&str>> = input.lines().sections().collect();
+
+        assert_eq!(
+            sections,
+            vec![
+                Section {
+                    header: "seeds: 79 14",
+                    body: vec![],
+                },
+                Section {
+                    header: "seed-to-soil map:",
+                    body: vec!["50 98 2", "52 50 48"],
+                },
+            ]
+        );
+
+        let sections: Vec<Section<String>> = io_lines(b"x:\n1\n")
+            .sections()
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(sections[0].header, "x:");
+        assert_eq!(sections[0].body, vec!["1"]);
+    }
+
+    #[test]
+    fn chunks_of_whole_size() {
+        let chunks: Vec<Vec<&str>> = "a\nb\nc\n\nd\ne"
+            .lines()
+            .chunks(3)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(chunks, vec![vec!["a", "b", "c"], vec!["", "d", "e"]]);
+
+        let chunks: Vec<Vec<String>> = io_lines(b"a\nb\n")
+            .chunks(1)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(chunks, vec![vec!["a"], vec!["b"]]);
+    }
+
+    #[test]
+    fn chunks_fail_on_partial_chunk() {
+        let mut chunks = "a\nb\nc\nd".lines().chunks(3);
+
+        assert_eq!(chunks.next(), Some(Ok(vec!["a", "b", "c"])));
+        let err = chunks.next().unwrap().unwrap_err();
+        assert_eq!(err, GroupError::PartialChunk { size: 3, len: 1 });
+        assert_eq!(err.to_string(), "the last chunk has 1 of 3 lines");
+        assert_eq!(chunks.next(), None);
+
+        let err = io_lines(b"a\n\xff\nc")
+            .chunks(3)
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(err, GroupError::Line(_)), "{err}");
+    }
+}
@@ -0,0 +1,26 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Resolves the input file for a day. Demo inputs can be specific to a part
+// (e.g. `day01/demo-input-part02.txt`) when the puzzle gives a different
+// example for each part, otherwise the day `demo-input.txt` is used.
+pub fn path(src_dir: &str, day: u8, part: u8, demo: bool) -> PathBuf {
+    let day_dir = Path::new(src_dir).join(format!("day{:02}", day));
+
+    if !demo {
+        return day_dir.join("input.txt");
+    }
+
+    let part_demo = day_dir.join(format!("demo-input-part{:02}.txt", part));
+    if part_demo.exists() {
+        return part_demo;
+    }
+
+    day_dir.join("demo-input.txt")
+}
+
+// Loads the whole input in memory so it can be given to any solution.
+pub fn load(src_dir: &str, day: u8, part: u8, demo: bool) -> io::Result<String> {
+    fs::read_to_string(path(src_dir, day, part, demo))
+}
@@ -0,0 +1,364 @@
+// Intervals of integers and sets of them. An interval is half-open, it
+// contains `start` but not `end`, so `Interval::new(10, 13)` holds 10, 11 and
+// 12, the same as `Interval::inclusive(10, 12)`. Intervals with `end <= start`
+// are empty.
+//
+// A `RangeSet` keeps its intervals normalized: sorted, not empty and neither
+// overlapping nor touching each other.
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Interval {
+        Interval { start, end }
+    }
+
+    // Interval from the first to the last value, both included, None when
+    // the last value is i64::MAX
+    pub fn inclusive(first: i64, last: i64) -> Option<Interval> {
+        Some(Interval::new(first, last.checked_add(1)?))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start).max(0) as u64
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    // Whether every value of the other interval is in this one
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    // Whether both intervals have at least one value in common
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start.max(other.start) < self.end.min(other.end)
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let interval = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!interval.is_empty()).then_some(interval)
+    }
+
+    // Single interval with the values of both, None when there is a gap
+    // between them.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.start.max(other.start) > self.end.min(other.end) {
+            return None;
+        }
+
+        Some(Interval::new(
+            self.start.min(other.start),
+            self.end.max(other.end),
+        ))
+    }
+
+    // Values of this interval not in the other, as the parts before and
+    // after it.
+    pub fn difference(&self, other: &Interval) -> (Option<Interval>, Option<Interval>) {
+        if !self.overlaps(other) {
+            let interval = (!self.is_empty()).then_some(*self);
+            return (interval, None);
+        }
+
+        let before = Interval::new(self.start, other.start);
+        let after = Interval::new(other.end, self.end);
+
+        (
+            (!before.is_empty()).then_some(before),
+            (!after.is_empty()).then_some(after),
+        )
+    }
+
+    // Parts of the interval below the value and from the value on
+    pub fn split_at(&self, value: i64) -> (Option<Interval>, Option<Interval>) {
+        let value = value.clamp(self.start, self.end.max(self.start));
+        let below = Interval::new(self.start, value);
+        let above = Interval::new(value, self.end);
+
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+
+    // Interval moved by the offset, None when it would go past the i64 range
+    pub fn translate(&self, offset: i64) -> Option<Interval> {
+        Some(Interval::new(
+            self.start.checked_add(offset)?,
+            self.end.checked_add(offset)?,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct RangeSet {
+    intervals: Vec<Interval>,
+}
+
+impl RangeSet {
+    pub fn new() -> RangeSet {
+        RangeSet::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // Number of values in the set
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(|i| i.len()).sum()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let index = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(index).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        self.intervals.push(interval);
+        self.normalize();
+    }
+
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        let intervals = self.intervals.iter().chain(&other.intervals).copied();
+        intervals.collect()
+    }
+
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            intervals.extend(a.intersection(&b));
+
+            match a.end < b.end {
+                true => i += 1,
+                false => j += 1,
+            }
+        }
+
+        RangeSet { intervals }
+    }
+
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        let mut intervals = vec![];
+
+        for interval in &self.intervals {
+            let mut rest = Some(*interval);
+
+            for removed in &other.intervals {
+                let Some(current) = rest else {
+                    break;
+                };
+                if removed.end <= current.start {
+                    continue;
+                }
+                if removed.start >= current.end {
+                    break;
+                }
+
+                let (before, after) = current.difference(removed);
+                intervals.extend(before);
+                rest = after;
+            }
+
+            intervals.extend(rest);
+        }
+
+        RangeSet { intervals }
+    }
+
+    // Sorts the intervals and merges the overlapping or touching ones
+    fn normalize(&mut self) {
+        self.intervals.retain(|i| !i.is_empty());
+        self.intervals.sort();
+
+        let mut merged: Vec<Interval> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+
+        self.intervals = merged;
+    }
+}
+
+impl FromIterator<Interval> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> RangeSet {
+        let mut set = RangeSet {
+            intervals: iter.into_iter().collect(),
+        };
+        set.normalize();
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+    use std::collections::BTreeSet;
+
+    // Values of the property tests stay within 0..LIMIT, so the intervals
+    // can be checked against plain sets of their values.
+    const LIMIT: i64 = 40;
+    const CASES: u64 = 1000;
+
+    fn interval(rng: &mut Rng) -> Interval {
+        let start = rng.range(0, LIMIT as u64) as i64;
+        let end = rng.range(0, LIMIT as u64) as i64;
+        Interval::new(start, end)
+    }
+
+    fn set(rng: &mut Rng) -> RangeSet {
+        (0..rng.range(0, 4)).map(|_| interval(rng)).collect()
+    }
+
+    fn values<I: IntoIterator<Item = Interval>>(intervals: I) -> BTreeSet<i64> {
+        intervals.into_iter().flat_map(|i| i.start..i.end).collect()
+    }
+
+    fn is_normalized(set: &RangeSet) -> bool {
+        set.intervals.iter().all(|i| !i.is_empty())
+            && set.intervals.windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    #[test]
+    fn inclusive_and_exclusive() {
+        let interval = Interval::inclusive(10, 12).unwrap();
+
+        assert_eq!(interval, Interval::new(10, 13));
+        assert_eq!(interval.len(), 3);
+        assert!(interval.contains(12));
+        assert!(!interval.contains(13));
+        assert!(Interval::new(5, 5).is_empty());
+        assert_eq!(Interval::new(5, 3).len(), 0);
+        assert_eq!(Interval::inclusive(1, i64::MAX), None);
+    }
+
+    #[test]
+    fn interval_operations() {
+        let a = Interval::new(2, 8);
+        let b = Interval::new(6, 10);
+
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&Interval::new(8, 9)));
+        assert_eq!(a.intersection(&b), Some(Interval::new(6, 8)));
+        assert_eq!(a.union(&b), Some(Interval::new(2, 10)));
+        assert_eq!(a.union(&Interval::new(8, 9)), Some(Interval::new(2, 9)));
+        assert_eq!(a.union(&Interval::new(9, 10)), None);
+        assert_eq!(
+            a.difference(&Interval::new(4, 5)),
+            (Some(Interval::new(2, 4)), Some(Interval::new(5, 8)))
+        );
+        assert_eq!(
+            a.split_at(3),
+            (Some(Interval::new(2, 3)), Some(Interval::new(3, 8)))
+        );
+        assert_eq!(a.split_at(20), (Some(a), None));
+        assert_eq!(a.translate(-2), Some(Interval::new(0, 6)));
+        assert_eq!(a.translate(i64::MAX), None);
+        assert_eq!(
+            a.translate(i64::MIN),
+            Some(Interval::new(i64::MIN + 2, i64::MIN + 8))
+        );
+    }
+
+    #[test]
+    fn range_set_merges() {
+        let set: RangeSet = [
+            Interval::new(5, 7),
+            Interval::new(1, 3),
+            Interval::new(3, 4),
+            Interval::new(6, 9),
+            Interval::new(20, 20),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(set.intervals(), &[Interval::new(1, 4), Interval::new(5, 9)]);
+        assert_eq!(set.len(), 7);
+        assert!(set.contains(8));
+        assert!(!set.contains(4));
+    }
+
+    #[test]
+    fn interval_properties() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..CASES {
+            let (a, b) = (interval(&mut rng), interval(&mut rng));
+            let (va, vb) = (values([a]), values([b]));
+            let value = rng.range(0, LIMIT as u64) as i64;
+
+            assert_eq!(a.len(), va.len() as u64);
+            assert_eq!(a.contains(value), va.contains(&value));
+            assert_eq!(a.contains_interval(&b), vb.is_subset(&va));
+            assert_eq!(a.overlaps(&b), !va.is_disjoint(&vb));
+            assert_eq!(values(a.intersection(&b)), &va & &vb);
+
+            if let Some(union) = a.union(&b) {
+                assert_eq!(values([union]), &va | &vb);
+            }
+
+            let (before, after) = a.difference(&b);
+            assert_eq!(values(before.into_iter().chain(after)), &va - &vb);
+
+            let (below, above) = a.split_at(value);
+            assert!(below.is_none_or(|i| i.end <= value));
+            assert!(above.is_none_or(|i| i.start >= value));
+            assert_eq!(values(below.into_iter().chain(above)), va);
+
+            let moved = values(a.translate(value));
+            assert_eq!(moved, va.iter().map(|v| v + value).collect());
+        }
+    }
+
+    #[test]
+    fn range_set_properties() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..CASES {
+            let (a, b) = (set(&mut rng), set(&mut rng));
+            let (va, vb) = (values(a.intervals.clone()), values(b.intervals.clone()));
+            let value = rng.range(0, LIMIT as u64) as i64;
+
+            assert_eq!(a.len(), va.len() as u64);
+            assert_eq!(a.contains(value), va.contains(&value));
+
+            for (set, expected) in [
+                (a.union(&b), &va | &vb),
+                (a.intersection(&b), &va & &vb),
+                (a.difference(&b), &va - &vb),
+            ] {
+                assert!(is_normalized(&set), "{:?} is not normalized", set);
+                assert_eq!(values(set.intervals.clone()), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// Runs a solution in a child process so a crash, a signal or running out of
+// memory can not take the runner down with it. The runner executable is run
+// again with the solution to serve in `AOC_ISOLATE`, gets the input on stdin
+// and writes its reply as a single JSON line to stdout:
+//
+//   AOC_ISOLATED {"result":{"Ok":"46"},"parse":null,"solve":{...},...}
+//
+// Before running the solution the child limits its address space to the
+// memory ceiling of the solution and its CPU time to the timeout, so the
+// kernel stops it when it goes past them. Every binary running solutions has
+// to call `serve` before anything else for the child to answer. Only built
+// on unix, where the limits and signals are available.
+use std::env;
+use std::io::{self, Read, Write};
+use std::os::unix::process::ExitStatusExt;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::Memory;
+use crate::runner::Crash;
+use crate::solution::{Registry, Run, Solution, Timing};
+
+const SOLUTION_VAR: &str = "AOC_ISOLATE";
+const LIMITS_VAR: &str = "AOC_ISOLATE_LIMITS";
+const REPLY_PREFIX: &str = "AOC_ISOLATED ";
+
+// How often the child is checked while waiting for it
+const POLL: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Reply {
+    result: Result<String, String>,
+    parse: Option<Duration>,
+    solve: Duration,
+    memory: Option<Memory>,
+    panic: Option<String>,
+}
+
+// The type of the resource differs between the libc of each system, so it
+// is given as an int and cast back
+fn set_limit(resource: libc::c_int, soft: u64, hard: u64) {
+    let limit = libc::rlimit {
+        rlim_cur: soft as libc::rlim_t,
+        rlim_max: hard as libc::rlim_t,
+    };
+
+    // SAFETY: setrlimit only reads the given struct
+    if unsafe { libc::setrlimit(resource as _, &limit) } != 0 {
+        eprintln!("Could not set limit: {}", io::Error::last_os_error());
+    }
+}
+
+fn reply(solution: &Solution, input: &str) -> Reply {
+    match panic::catch_unwind(AssertUnwindSafe(|| solution.run(input))) {
+        Ok(run) => Reply {
+            result: run.result,
+            parse: run.timing.parse,
+            solve: run.timing.solve,
+            memory: run.memory,
+            panic: None,
+        },
+        Err(payload) => {
+            let message = match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(payload) => match payload.downcast::<&str>() {
+                    Ok(message) => message.to_string(),
+                    Err(_) => "".to_string(),
+                },
+            };
+
+            Reply {
+                result: Err("panicked".to_string()),
+                parse: None,
+                solve: Duration::ZERO,
+                memory: None,
+                panic: Some(message),
+            }
+        }
+    }
+}
+
+// Answers as the child process when the runner asked to isolate a solution
+// of the registry, exiting once done. Does nothing otherwise.
+pub fn serve(registry: &Registry) {
+    let Ok(name) = env::var(SOLUTION_VAR) else {
+        return;
+    };
+
+    let solution = registry
+        .solutions
+        .iter()
+        .find(|s| format!("{}/{}", s.year, s.name()) == name);
+    let Some(solution) = solution else {
+        return;
+    };
+
+    let limits: Vec<u64> = env::var(LIMITS_VAR)
+        .unwrap_or_default()
+        .split(' ')
+        .filter_map(|n| n.parse().ok())
+        .collect();
+    if let [memory, cpu_secs] = limits[..] {
+        set_limit(libc::RLIMIT_AS as libc::c_int, memory, memory);
+        set_limit(libc::RLIMIT_CPU as libc::c_int, cpu_secs, cpu_secs + 1);
+    }
+
+    let mut input = String::new();
+    if let Err(err) = io::stdin().read_to_string(&mut input) {
+        eprintln!("Could not read input: {}", err);
+        process::exit(2);
+    }
+
+    let reply = reply(solution, &input);
+    let code = if reply.panic.is_some() { 101 } else { 0 };
+
+    let line = serde_json::to_string(&reply).expect("Could not serialize reply");
+    let mut stdout = io::stdout().lock();
+    let _ = writeln!(stdout, "{}{}", REPLY_PREFIX, line);
+    let _ = stdout.flush();
+
+    process::exit(code);
+}
+
+// Why the child stopped, from its reply, exit status and error output
+fn crash(reply: &Option<Reply>, status: process::ExitStatus, stderr: &str) -> Crash {
+    if let Some(message) = reply.as_ref().and_then(|r| r.panic.clone()) {
+        return Crash::Panic { message };
+    }
+    if stderr.contains("memory allocation of") {
+        return Crash::OutOfMemory;
+    }
+
+    match (status.signal(), status.code()) {
+        (Some(libc::SIGXCPU), _) => Crash::CpuLimit,
+        (Some(signal), _) => Crash::Signal { signal },
+        (None, Some(code)) => Crash::Exit { code },
+        (None, None) => Crash::Exit { code: -1 },
+    }
+}
+
+// Runs the solution with the command, which must call `serve` for its
+// registry. The limits come from the budget of the solution.
+pub fn run(mut command: Command, solution: &Solution, input: &str) -> Result<Run, Crash> {
+    let budget = solution.budget;
+    let cpu_secs = budget.timeout().as_secs().max(1);
+
+    let mut child = command
+        .env(
+            SOLUTION_VAR,
+            format!("{}/{}", solution.year, solution.name()),
+        )
+        .env(LIMITS_VAR, format!("{} {}", budget.memory, cpu_secs))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| Crash::Spawn {
+            error: err.to_string(),
+        })?;
+
+    let mut stdin = child.stdin.take().expect("Missing child stdin");
+    let input = input.to_string();
+    thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let read = |mut pipe: Box<dyn Read + Send>| {
+        thread::spawn(move || {
+            let mut text = String::new();
+            let _ = pipe.read_to_string(&mut text);
+            text
+        })
+    };
+    let stdout = read(Box::new(child.stdout.take().expect("Missing child stdout")));
+    let stderr = read(Box::new(child.stderr.take().expect("Missing child stderr")));
+
+    let deadline = Instant::now() + budget.timeout();
+    let status = loop {
+        if let Ok(Some(status)) = child.try_wait() {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Crash::Timeout);
+        }
+        thread::sleep(POLL);
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    let reply: Option<Reply> = stdout
+        .lines()
+        .rev()
+        .find_map(|line| line.split_once(REPLY_PREFIX))
+        .map(|(_, line)| line)
+        .and_then(|line| serde_json::from_str(line).ok());
+
+    match reply {
+        Some(reply) if status.success() => {
+            // Logs of the solution
+            eprint!("{}", stderr);
+
+            Ok(Run {
+                result: reply.result,
+                timing: Timing {
+                    parse: reply.parse,
+                    solve: reply.solve,
+                },
+                memory: reply.memory,
+            })
+        }
+        reply => Err(crash(&reply, status, &stderr)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::Answers;
+    use crate::solution::Budget;
+
+    fn registry() -> Registry {
+        let budget = Budget {
+            time: Duration::from_millis(50),
+            memory: 256 << 20,
+        };
+        let solutions: Vec<Solution> = vec![
+            Solution::new(2022, 1, 1, 1, |input| Ok(input.trim().to_string())),
+            Solution::new(2022, 1, 2, 1, |_| panic!("boom")),
+            Solution::new(2022, 2, 1, 1, |_| Ok(vec![1u8; 1 << 30].len().to_string())),
+            Solution::new(2022, 2, 2, 1, |_| process::abort()),
+            Solution::new(2022, 3, 1, 1, |_| loop {
+                thread::sleep(Duration::from_millis(100));
+            }),
+        ];
+
+        Registry {
+            year: 2022,
+            src_dir: "",
+            solutions: solutions
+                .into_iter()
+                .map(|s| s.with_budget(budget))
+                .collect(),
+            answers: Answers::default(),
+            generators: vec![],
+        }
+    }
+
+    // Serves the isolated solutions when this test binary is run as a child
+    #[test]
+    fn child() {
+        serve(&registry());
+    }
+
+    fn isolated(solution: &Solution) -> Result<Run, Crash> {
+        let mut command = Command::new(env::current_exe().unwrap());
+        command.args(["isolate::tests::child", "--exact", "--nocapture"]);
+        run(command, solution, "42\n")
+    }
+
+    #[test]
+    fn isolated_runs() {
+        let registry = registry();
+        let solution = |day, part| registry.find(day, part, 1).unwrap();
+
+        let run = isolated(solution(1, 1)).unwrap();
+        assert_eq!(run.result, Ok("42".to_string()));
+
+        assert_eq!(
+            isolated(solution(1, 2)).err(),
+            Some(Crash::Panic {
+                message: "boom".to_string()
+            })
+        );
+        assert_eq!(isolated(solution(2, 1)).err(), Some(Crash::OutOfMemory));
+        assert_eq!(
+            isolated(solution(2, 2)).err(),
+            Some(Crash::Signal {
+                signal: libc::SIGABRT
+            })
+        );
+        assert_eq!(isolated(solution(3, 1)).err(), Some(Crash::Timeout));
+    }
+}
@@ -0,0 +1,26 @@
+// Lets the code generated by aoc_macros refer to this crate from its own tests
+extern crate self as aoc_common;
+
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod format;
+pub mod fuzz;
+pub mod graph;
+pub mod grid;
+pub mod group;
+pub mod input;
+pub mod interval;
+#[cfg(unix)]
+pub mod isolate;
+pub mod log;
+pub mod math;
+pub mod numbers;
+pub mod output;
+pub mod parsed;
+pub mod pattern;
+pub mod property;
+pub mod random;
+pub mod runner;
+pub mod snapshot;
+pub mod solution;
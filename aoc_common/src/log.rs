@@ -0,0 +1,222 @@
+// Lightweight leveled logging for the solutions.
+//
+// Solutions log through the `error!`, `warn!`, `info!`, `debug!` and `trace!`
+// macros. Nothing is printed unless a filter is set from the command line,
+// for example `--log 2022::day05=trace,2023=debug` or just `--log debug`.
+// Targets are the module path of the caller with the year crate name
+// shortened to the year, so `aoc_2022_rust::day05::part01` becomes
+// `2022::day05::part01`. Logs are written to stderr so they never mix with
+// the results printed to stdout.
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    fn label(&self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Level, String> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("invalid log level {:?}", s)),
+        }
+    }
+}
+
+// A list of `target=level` directives. A directive without a target applies
+// to every target, and the most specific matching directive wins.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Filter {
+    directives: Vec<(Option<String>, Level)>,
+}
+
+impl Filter {
+    pub fn parse(spec: &str) -> Result<Filter, String> {
+        let mut directives = vec![];
+
+        for part in spec.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            let directive = match part.split_once('=') {
+                Some((target, level)) => (Some(target.trim().to_string()), level.trim().parse()?),
+                None => match part.parse() {
+                    Ok(level) => (None, level),
+                    // A target alone enables all its logs
+                    Err(_) => (Some(part.to_string()), Level::Trace),
+                },
+            };
+
+            directives.push(directive);
+        }
+
+        Ok(Filter { directives })
+    }
+
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        let mut best: Option<(usize, Level)> = None;
+
+        for (directive_target, directive_level) in &self.directives {
+            let specificity = match directive_target {
+                None => 0,
+                Some(t) if matches_target(t, target) => t.len() + 1,
+                Some(_) => continue,
+            };
+
+            if best.map(|(s, _)| specificity >= s).unwrap_or(true) {
+                best = Some((specificity, *directive_level));
+            }
+        }
+
+        best.map(|(_, max)| level <= max).unwrap_or(false)
+    }
+}
+
+// The directive target must match whole path segments of the target.
+fn matches_target(directive: &str, target: &str) -> bool {
+    match target.strip_prefix(directive) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
+    }
+}
+
+// Shortens the year crate names (e.g. `aoc_2022_rust`) to the year.
+pub fn target(module_path: &str) -> String {
+    let (krate, rest) = match module_path.split_once("::") {
+        Some((krate, rest)) => (krate, Some(rest)),
+        None => (module_path, None),
+    };
+
+    let krate = krate
+        .strip_prefix("aoc_")
+        .and_then(|k| k.strip_suffix("_rust"))
+        .unwrap_or(krate);
+
+    match rest {
+        Some(rest) => format!("{}::{}", krate, rest),
+        None => krate.to_string(),
+    }
+}
+
+static FILTER: OnceLock<Filter> = OnceLock::new();
+
+// Sets the global filter. Only the first call has any effect.
+pub fn init(filter: Filter) {
+    let _ = FILTER.set(filter);
+}
+
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    match FILTER.get() {
+        Some(filter) => filter.enabled(level, &target(module_path)),
+        None => false,
+    }
+}
+
+pub fn write(level: Level, module_path: &str, args: fmt::Arguments) {
+    eprintln!("[{} {}] {}", level.label(), target(module_path), args);
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level, module_path!()) {
+            $crate::log::write($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_filter() {
+        let filter = Filter::parse("warn, 2022::day05=trace,2023").unwrap();
+        assert_eq!(
+            filter.directives,
+            vec![
+                (None, Level::Warn),
+                (Some("2022::day05".to_string()), Level::Trace),
+                (Some("2023".to_string()), Level::Trace),
+            ]
+        );
+
+        assert!(Filter::parse("2022::day05=loud").is_err());
+    }
+
+    #[test]
+    fn filter_enabled() {
+        let filter = Filter::parse("info,2022::day05=trace,2022::day05::part02=error").unwrap();
+
+        assert!(filter.enabled(Level::Trace, "2022::day05"));
+        assert!(filter.enabled(Level::Trace, "2022::day05::part01"));
+        assert!(!filter.enabled(Level::Debug, "2022::day05::part02"));
+        assert!(filter.enabled(Level::Error, "2022::day05::part02"));
+
+        // falls back to the default level
+        assert!(filter.enabled(Level::Info, "2022::day04::part01"));
+        assert!(!filter.enabled(Level::Debug, "2022::day04::part01"));
+
+        // only whole segments are matched
+        assert!(!filter.enabled(Level::Trace, "2022::day050"));
+
+        // nothing is enabled without directives
+        assert!(!Filter::default().enabled(Level::Error, "2022::day05"));
+    }
+
+    #[test]
+    fn target_from_module_path() {
+        assert_eq!(
+            target("aoc_2022_rust::day05::part01"),
+            "2022::day05::part01"
+        );
+        assert_eq!(target("aoc_2023_rust"), "2023");
+        assert_eq!(target("aoc_common::runner"), "aoc_common::runner");
+    }
+}
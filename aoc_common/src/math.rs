@@ -0,0 +1,404 @@
+// Number theory for the puzzles aligning cycles: gcd and lcm, extended
+// Euclid, the Chinese remainder theorem, modular powers and inverses and
+// integer square roots.
+//
+// Functions that can overflow have a `checked_` version returning
+// MathError::Overflow instead, the plain version panics with a message.
+// Modular arithmetic is done on i128 or u128 so it never overflows for
+// 64-bit inputs.
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MathError {
+    Overflow,
+    // Moduli have to be positive
+    InvalidModulus(i64),
+    NotInvertible { value: i64, modulus: i64 },
+    NoSolution,
+    Negative,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MathError::Overflow => write!(f, "the result overflows"),
+            MathError::InvalidModulus(m) => write!(f, "modulus {m} is not positive"),
+            MathError::NotInvertible { value, modulus } => {
+                write!(f, "{value} has no inverse modulo {modulus}")
+            }
+            MathError::NoSolution => write!(f, "the congruences have no solution"),
+            MathError::Negative => write!(f, "negative numbers have no square root"),
+        }
+    }
+}
+
+pub trait Integer: Copy + Ord + fmt::Debug {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_isqrt(self) -> Option<Self>;
+}
+
+// Integers without sign, which have a gcd and lcm
+pub trait Unsigned: Integer {
+    fn rem(self, other: Self) -> Self;
+    fn div(self, other: Self) -> Self;
+}
+
+macro_rules! signed {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: $t = 0;
+                const ONE: $t = 1;
+
+                fn checked_add(self, other: $t) -> Option<$t> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: $t) -> Option<$t> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_isqrt(self) -> Option<$t> {
+                    <$t>::checked_isqrt(self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: $t = 0;
+                const ONE: $t = 1;
+
+                fn checked_add(self, other: $t) -> Option<$t> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: $t) -> Option<$t> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_isqrt(self) -> Option<$t> {
+                    Some(<$t>::isqrt(self))
+                }
+            }
+
+            impl Unsigned for $t {
+                fn rem(self, other: $t) -> $t {
+                    self % other
+                }
+
+                fn div(self, other: $t) -> $t {
+                    self / other
+                }
+            }
+        )*
+    };
+}
+
+signed!(i8, i16, i32, i64, i128, isize);
+unsigned!(u8, u16, u32, u64, u128, usize);
+
+pub fn checked_sum<T, I>(values: I) -> Result<T, MathError>
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().try_fold(T::ZERO, |total, value| {
+        total.checked_add(value).ok_or(MathError::Overflow)
+    })
+}
+
+pub fn checked_product<T, I>(values: I) -> Result<T, MathError>
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().try_fold(T::ONE, |total, value| {
+        total.checked_mul(value).ok_or(MathError::Overflow)
+    })
+}
+
+pub fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a.rem(b));
+    }
+    a
+}
+
+// Zero when any of the values is zero
+pub fn checked_lcm<T: Unsigned>(a: T, b: T) -> Result<T, MathError> {
+    if a == T::ZERO || b == T::ZERO {
+        return Ok(T::ZERO);
+    }
+
+    a.div(gcd(a, b)).checked_mul(b).ok_or(MathError::Overflow)
+}
+
+pub fn lcm<T: Unsigned>(a: T, b: T) -> T {
+    checked_lcm(a, b).unwrap_or_else(|_| panic!("lcm of {a:?} and {b:?} overflows"))
+}
+
+// Zero for no values
+pub fn gcd_all<T: Unsigned, I: IntoIterator<Item = T>>(values: I) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+// One for no values
+pub fn checked_lcm_all<T, I>(values: I) -> Result<T, MathError>
+where
+    T: Unsigned,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().try_fold(T::ONE, checked_lcm)
+}
+
+pub fn lcm_all<T: Unsigned, I: IntoIterator<Item = T>>(values: I) -> T {
+    checked_lcm_all(values).unwrap_or_else(|_| panic!("lcm of the values overflows"))
+}
+
+// The gcd of a and b with x and y such that `a * x + b * y = gcd`, the gcd
+// being never negative.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    match old_r < 0 {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+// The base to the power of exp, modulo the modulus
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> Result<u64, MathError> {
+    if modulus == 0 {
+        return Err(MathError::InvalidModulus(0));
+    }
+
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    Ok(result as u64)
+}
+
+// The x in `0..modulus` such that `value * x ≡ 1 (mod modulus)`
+pub fn mod_inverse(value: i64, modulus: i64) -> Result<i64, MathError> {
+    if modulus <= 0 {
+        return Err(MathError::InvalidModulus(modulus));
+    }
+
+    let (g, x, _) = extended_gcd(value as i128, modulus as i128);
+    if g != 1 {
+        return Err(MathError::NotInvertible { value, modulus });
+    }
+
+    Ok(x.rem_euclid(modulus as i128) as i64)
+}
+
+// Solves `x ≡ residue (mod modulus)` for every (residue, modulus) given,
+// giving the smallest non-negative x and the lcm of the moduli, every
+// solution being x plus a multiple of it. Moduli do not need to be coprime.
+pub fn crt<I: IntoIterator<Item = (i64, i64)>>(congruences: I) -> Result<(i64, i64), MathError> {
+    let (mut x, mut m): (i128, i128) = (0, 1);
+
+    for (residue, modulus) in congruences {
+        if modulus <= 0 {
+            return Err(MathError::InvalidModulus(modulus));
+        }
+        let (r2, m2) = (
+            (residue as i128).rem_euclid(modulus as i128),
+            modulus as i128,
+        );
+
+        let (g, inverse, _) = extended_gcd(m, m2);
+        let diff = r2 - x;
+        if diff % g != 0 {
+            return Err(MathError::NoSolution);
+        }
+
+        // x + m * t solves both when `m * t ≡ diff (mod m2)`
+        let step = m2 / g;
+        let t = (diff / g).rem_euclid(step) * inverse.rem_euclid(step) % step;
+        let lcm = m * step;
+        if lcm > i64::MAX as i128 {
+            return Err(MathError::Overflow);
+        }
+
+        x = (x + m * t).rem_euclid(lcm);
+        m = lcm;
+    }
+
+    Ok((x as i64, m as i64))
+}
+
+// Largest integer whose square is at most n
+pub fn checked_isqrt<T: Integer>(n: T) -> Result<T, MathError> {
+    n.checked_isqrt().ok_or(MathError::Negative)
+}
+
+pub fn isqrt<T: Integer>(n: T) -> T {
+    checked_isqrt(n).unwrap_or_else(|_| panic!("{n:?} has no square root"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(0u32, 7), 7);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4usize, 6), 12);
+        assert_eq!(lcm(0u64, 6), 0);
+
+        assert_eq!(gcd_all([12u64, 18, 27]), 3);
+        assert_eq!(gcd_all(Vec::<u64>::new()), 0);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u64>::new()), 1);
+    }
+
+    #[test]
+    fn lcm_overflow() {
+        assert_eq!(checked_lcm(200u8, 3), Err(MathError::Overflow));
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX), Ok(u64::MAX));
+        assert_eq!(
+            checked_lcm_all([u32::MAX, u32::MAX - 1]),
+            Err(MathError::Overflow)
+        );
+        assert!(std::panic::catch_unwind(|| lcm(u64::MAX, u64::MAX - 1)).is_err());
+    }
+
+    #[test]
+    fn checked_sums_and_products() {
+        assert_eq!(checked_sum([1usize, 2, 3]), Ok(6));
+        assert_eq!(checked_sum(Vec::<i32>::new()), Ok(0));
+        assert_eq!(checked_sum([-5i8, -100, -30]), Err(MathError::Overflow));
+        assert_eq!(checked_sum([usize::MAX, 1]), Err(MathError::Overflow));
+
+        assert_eq!(checked_product([2u64, 3, 7]), Ok(42));
+        assert_eq!(checked_product(Vec::<u8>::new()), Ok(1));
+        assert_eq!(checked_product([16u8, 16]), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn extended_gcd_coefficients() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(0, 0).0, 0);
+
+        let mut rng = Rng::new(5);
+        for _ in 0..1000 {
+            let a = rng.range(0, 1 << 40) as i128 - (1 << 39);
+            let b = rng.range(0, 1 << 40) as i128 - (1 << 39);
+            let (g, x, y) = extended_gcd(a, b);
+
+            assert!(g >= 0);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(g as u128, gcd(a.unsigned_abs(), b.unsigned_abs()));
+        }
+    }
+
+    #[test]
+    fn modular_powers() {
+        assert_eq!(mod_pow(4, 13, 497), Ok(445));
+        assert_eq!(mod_pow(7, 0, 13), Ok(1));
+        assert_eq!(mod_pow(7, 0, 1), Ok(0));
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), Ok(1));
+        assert_eq!(mod_pow(2, 3, 0), Err(MathError::InvalidModulus(0)));
+
+        let mut rng = Rng::new(9);
+        for _ in 0..100 {
+            let (base, exp, modulus) = (rng.next_u64(), rng.range(0, 50), rng.range(1, 1000));
+            let naive = (0..exp).fold(1 % modulus, |acc, _| acc * (base % modulus) % modulus);
+            assert_eq!(mod_pow(base, exp, modulus), Ok(naive));
+        }
+    }
+
+    #[test]
+    fn modular_inverses() {
+        assert_eq!(mod_inverse(3, 11), Ok(4));
+        assert_eq!(mod_inverse(-3, 11), Ok(7));
+        assert_eq!(mod_inverse(1, 1), Ok(0));
+        assert_eq!(
+            mod_inverse(6, 9),
+            Err(MathError::NotInvertible {
+                value: 6,
+                modulus: 9,
+            })
+        );
+        assert_eq!(mod_inverse(2, 0), Err(MathError::InvalidModulus(0)));
+
+        let modulus = 1_000_000_007;
+        let inverse = mod_inverse(123_456_789, modulus).unwrap();
+        assert_eq!(123_456_789i128 * inverse as i128 % modulus as i128, 1);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        assert_eq!(crt([(-1, 4)]), Ok((3, 4)));
+        assert_eq!(crt([]), Ok((0, 1)));
+
+        // Moduli sharing factors
+        assert_eq!(crt([(3, 4), (5, 6)]), Ok((11, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), Err(MathError::NoSolution));
+
+        assert_eq!(crt([(1, -5)]), Err(MathError::InvalidModulus(-5)));
+        assert_eq!(
+            crt([(0, 4_000_000_007), (0, 4_000_000_009), (1, 3)]),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn chinese_remainders_of_random_values() {
+        let mut rng = Rng::new(13);
+
+        for _ in 0..1000 {
+            let x = rng.range(0, 1 << 40) as i64;
+            let moduli: Vec<i64> = (0..rng.range(1, 4))
+                .map(|_| rng.range(1, 300) as i64)
+                .collect();
+            let (solution, modulus) = crt(moduli.iter().map(|&m| (x % m, m))).unwrap();
+
+            let expected = moduli.iter().fold(1u64, |acc, &m| lcm(acc, m as u64));
+            assert_eq!(modulus as u64, expected);
+            assert_eq!(solution, x % modulus);
+        }
+    }
+
+    #[test]
+    fn integer_square_roots() {
+        assert_eq!(isqrt(0u64), 0);
+        assert_eq!(isqrt(15u64), 3);
+        assert_eq!(isqrt(16u64), 4);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt(99i32), 9);
+        assert_eq!(checked_isqrt(-1i64), Err(MathError::Negative));
+    }
+}
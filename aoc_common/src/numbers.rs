@@ -0,0 +1,188 @@
+// Extracts every integer of a text, whatever separates them, e.g.
+// `Card 1: 41 48 | 83 -6` has the numbers 1, 41, 48, 83 and -6.
+//
+// A `-` or `+` right before the digits is the sign of the number, unless it
+// follows a letter or digit as in `2-8` or `a-1`, where it only separates
+// them. Numbers are parsed into the requested type, so a negative number for
+// an unsigned type or one too big for the type is an error instead of being
+// dropped.
+use std::fmt;
+use std::num::{IntErrorKind, ParseIntError};
+use std::ops::Range;
+use std::str::FromStr;
+
+// A number of the text with the byte range it was found at
+#[derive(Clone, Debug, PartialEq)]
+pub struct Number<T> {
+    pub value: T,
+    pub span: Range<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberError {
+    pub token: String,
+    pub span: Range<usize>,
+    pub kind: IntErrorKind,
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.kind {
+            IntErrorKind::PosOverflow => "is too big",
+            IntErrorKind::NegOverflow => "is too small",
+            _ => "is not valid",
+        };
+        write!(f, "number {} at {:?} {}", self.token, self.span, reason)
+    }
+}
+
+// Byte ranges of the number tokens of the text, signs included
+fn tokens(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut start = i;
+        let signed = start > 0 && matches!(bytes[start - 1], b'-' | b'+');
+        if signed && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric()) {
+            start -= 1;
+        }
+
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        tokens.push(start..i);
+    }
+
+    tokens
+}
+
+// All the numbers of the text with their positions.
+pub fn extract<T>(text: &str) -> Result<Vec<Number<T>>, NumberError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    tokens(text)
+        .into_iter()
+        .map(|span| {
+            let token = &text[span.clone()];
+            match token.parse::<T>() {
+                Ok(value) => Ok(Number { value, span }),
+                Err(err) => Err(NumberError {
+                    token: token.to_string(),
+                    span,
+                    kind: *err.kind(),
+                }),
+            }
+        })
+        .collect()
+}
+
+// All the numbers of the text.
+pub fn values<T>(text: &str) -> Result<Vec<T>, NumberError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    let numbers = extract(text)?;
+    Ok(numbers.into_iter().map(|n| n.value).collect())
+}
+
+// The number making up the whole text, like a field of a line. Text around
+// or between numbers makes it invalid, and an empty text is `Empty`.
+pub fn whole<T>(text: &str) -> Result<T, NumberError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    let mut numbers = extract(text)?;
+    if numbers.len() == 1 && numbers[0].span == (0..text.len()) {
+        return Ok(numbers.remove(0).value);
+    }
+
+    let kind = if text.is_empty() {
+        IntErrorKind::Empty
+    } else {
+        IntErrorKind::InvalidDigit
+    };
+    Err(NumberError {
+        token: text.to_string(),
+        span: 0..text.len(),
+        kind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_numbers() {
+        assert_eq!(
+            values::<i64>("Card 1: 41 48 |  0 83 -6"),
+            Ok(vec![1, 41, 48, 0, 83, -6])
+        );
+        assert_eq!(values::<i32>("2-8,+3-7"), Ok(vec![2, 8, 3, 7]));
+        assert_eq!(values::<u8>("x=-y, a-1"), Ok(vec![1]));
+        assert_eq!(values::<u8>("no numbers"), Ok(vec![]));
+    }
+
+    #[test]
+    fn number_positions() {
+        assert_eq!(
+            extract::<i32>("move -1 from 23"),
+            Ok(vec![
+                Number {
+                    value: -1,
+                    span: 5..7,
+                },
+                Number {
+                    value: 23,
+                    span: 13..15,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn out_of_range_numbers() {
+        let err = values::<u8>("1 256").unwrap_err();
+        assert_eq!(err.span, 2..5);
+        assert_eq!(err.kind, IntErrorKind::PosOverflow);
+        assert_eq!(err.to_string(), "number 256 at 2..5 is too big");
+
+        let err = values::<u32>("3 -4").unwrap_err();
+        assert_eq!(err.token, "-4");
+        assert_eq!(err.kind, IntErrorKind::InvalidDigit);
+
+        assert_eq!(values::<i8>("-128 127"), Ok(vec![-128, 127]));
+        assert_eq!(
+            values::<i8>("-129").unwrap_err().kind,
+            IntErrorKind::NegOverflow
+        );
+    }
+
+    #[test]
+    fn whole_numbers() {
+        assert_eq!(whole::<i64>("-3"), Ok(-3));
+        assert_eq!(whole::<u8>("+7"), Ok(7));
+        assert_eq!(
+            whole::<u8>("300").unwrap_err().kind,
+            IntErrorKind::PosOverflow
+        );
+        assert_eq!(
+            whole::<u32>("-5").unwrap_err().kind,
+            IntErrorKind::InvalidDigit
+        );
+        assert_eq!(whole::<u32>("3 now").unwrap_err().span, 0..5);
+        assert_eq!(
+            whole::<u32>("1 2").unwrap_err().kind,
+            IntErrorKind::InvalidDigit
+        );
+        assert_eq!(whole::<u32>("").unwrap_err().kind, IntErrorKind::Empty);
+    }
+}
@@ -0,0 +1,158 @@
+use clap::ValueEnum;
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, IsTerminal};
+
+use crate::runner::Status;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ColorChoice {
+    // Colors only when writing to a terminal and NO_COLOR is not set
+    Auto,
+    Always,
+    Never,
+}
+
+// The states a solution goes through in the runner output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum State {
+    Running,
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl From<Status> for State {
+    fn from(status: Status) -> State {
+        match status {
+            Status::Passed => State::Passed,
+            Status::Failed | Status::Error => State::Failed,
+            Status::Unknown => State::Skipped,
+        }
+    }
+}
+
+// Stars of a day in the calendar, gold when both parts are solved and
+// silver when only one of them is.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Star {
+    Gold,
+    Silver,
+    Empty,
+}
+
+// Icon and ANSI color code used for each state.
+struct Style {
+    icon: &'static str,
+    color: &'static str,
+}
+
+fn style(state: State) -> Style {
+    match state {
+        State::Running => Style {
+            icon: "•",
+            color: "0;93",
+        },
+        State::Passed => Style {
+            icon: "✓",
+            color: "0;32",
+        },
+        State::Failed => Style {
+            icon: "✗",
+            color: "0;31",
+        },
+        State::Skipped => Style {
+            icon: "-",
+            color: "0;90",
+        },
+    }
+}
+
+// Decorates the runner output, only adding escape codes when colors are enabled.
+#[derive(Clone, Copy, Debug)]
+pub struct Output {
+    color: bool,
+}
+
+impl Output {
+    // Resolves the color choice against the environment and stdout.
+    pub fn new(choice: ColorChoice) -> Output {
+        Output {
+            color: use_color(choice, env::var_os("NO_COLOR"), io::stdout().is_terminal()),
+        }
+    }
+
+    pub fn plain() -> Output {
+        Output { color: false }
+    }
+
+    pub fn icon(&self, state: State) -> String {
+        self.paint(state, style(state).icon)
+    }
+
+    pub fn paint(&self, state: State, text: &str) -> String {
+        self.paint_code(style(state).color, text)
+    }
+
+    pub fn star(&self, star: Star, text: &str) -> String {
+        let color = match star {
+            Star::Gold => "0;93",
+            Star::Silver => "0;94",
+            Star::Empty => "0;90",
+        };
+
+        self.paint_code(color, text)
+    }
+
+    fn paint_code(&self, color: &str, text: &str) -> String {
+        if !self.color {
+            return text.to_string();
+        }
+
+        format!("\x1b[{}m{}\x1b[0;00m", color, text)
+    }
+}
+
+// NO_COLOR is only honored when it is set to a non empty value (https://no-color.org).
+fn use_color(choice: ColorChoice, no_color: Option<OsString>, is_tty: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            let no_color = no_color.map(|v| !v.is_empty()).unwrap_or(false);
+            is_tty && !no_color
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn use_color_choice() {
+        let set = || Some(OsString::from("1"));
+        let empty = || Some(OsString::from(""));
+
+        assert!(use_color(ColorChoice::Always, set(), false));
+        assert!(!use_color(ColorChoice::Never, None, true));
+
+        assert!(use_color(ColorChoice::Auto, None, true));
+        assert!(use_color(ColorChoice::Auto, empty(), true));
+        assert!(!use_color(ColorChoice::Auto, set(), true));
+        assert!(!use_color(ColorChoice::Auto, None, false));
+    }
+
+    #[test]
+    fn paint() {
+        let colored = Output { color: true };
+        assert_eq!(colored.icon(State::Passed), "\x1b[0;32m✓\x1b[0;00m");
+        assert_eq!(colored.paint(State::Failed, "x"), "\x1b[0;31mx\x1b[0;00m");
+
+        assert_eq!(colored.star(Star::Gold, "*"), "\x1b[0;93m*\x1b[0;00m");
+
+        let plain = Output::plain();
+        assert_eq!(plain.icon(State::Running), "•");
+        assert_eq!(plain.paint(State::Skipped, "x"), "x");
+    }
+}
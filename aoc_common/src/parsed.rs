@@ -0,0 +1,173 @@
+// Snapshot tests of parsed structures. The value is serialized to pretty JSON
+// and compared with the file checked in at `snapshots/<module>_<name>.json`
+// of the crate, for example `snapshots/day05_part01_garden_info.json` for
+// `assert_parsed!("garden_info", info)` in `day05::part01`.
+//
+// A mismatch or a missing snapshot fails the test with a line diff and writes
+// the new value next to it as `<file>.new` for review. Running the tests with
+// `AOC_ACCEPT_SNAPSHOTS=1` writes every new value over its snapshot.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+// Snapshot file of a test, the module path without the crate name and the
+// `tests` module joined with the name.
+pub fn path(crate_dir: &str, module_path: &str, name: &str) -> PathBuf {
+    let mut parts: Vec<&str> = module_path
+        .split("::")
+        .skip(1)
+        .filter(|part| *part != "tests")
+        .collect();
+    parts.push(name);
+
+    Path::new(crate_dir)
+        .join("snapshots")
+        .join(format!("{}.json", parts.join("_")))
+}
+
+pub fn to_json<T: Serialize>(value: &T) -> String {
+    let json = serde_json::to_string_pretty(value).expect("Could not serialize value");
+    format!("{}\n", json)
+}
+
+// Line diff of two texts from their longest common subsequence, with removed
+// lines prefixed by `-`, added ones by `+` and common ones by a space.
+pub fn diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] is the common length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = match old[i] == new[j] {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out += &format!(" {}\n", old[i]);
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out += &format!("-{}\n", old[i]);
+            i += 1;
+        } else {
+            out += &format!("+{}\n", new[j]);
+            j += 1;
+        }
+    }
+
+    out
+}
+
+fn accepting() -> bool {
+    env::var("AOC_ACCEPT_SNAPSHOTS").is_ok_and(|v| v != "0" && !v.is_empty())
+}
+
+pub fn assert<T: Serialize>(crate_dir: &str, module_path: &str, name: &str, value: &T) {
+    let path = path(crate_dir, module_path, name);
+    let new_path = path.with_extension("json.new");
+    let actual = to_json(value);
+
+    if accepting() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).expect("Could not create snapshots directory");
+        }
+        fs::write(&path, &actual).expect("Could not write snapshot");
+        let _ = fs::remove_file(&new_path);
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).ok();
+    if expected.as_deref() == Some(actual.as_str()) {
+        let _ = fs::remove_file(&new_path);
+        return;
+    }
+
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let _ = fs::write(&new_path, &actual);
+
+    match expected {
+        Some(expected) => panic!(
+            "Snapshot {:?} changed, accept with AOC_ACCEPT_SNAPSHOTS=1 cargo test:\n{}",
+            path,
+            diff(&expected, &actual)
+        ),
+        None => panic!(
+            "Missing snapshot {:?}, accept with AOC_ACCEPT_SNAPSHOTS=1 cargo test:\n{}",
+            path, actual
+        ),
+    }
+}
+
+// Asserts a value matches its snapshot in the calling crate.
+#[macro_export]
+macro_rules! assert_parsed {
+    ($name:expr, $value:expr) => {
+        $crate::parsed::assert(env!("CARGO_MANIFEST_DIR"), module_path!(), $name, &$value)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_path() {
+        assert_eq!(
+            path(
+                "/code/aoc_2023_rust",
+                "aoc_2023_rust::day05::part01::tests",
+                "garden_info"
+            ),
+            PathBuf::from("/code/aoc_2023_rust/snapshots/day05_part01_garden_info.json")
+        );
+    }
+
+    #[test]
+    fn json_is_pretty() {
+        assert_eq!(
+            to_json(&vec![(1, 'a')]),
+            "[\n  [\n    1,\n    \"a\"\n  ]\n]\n"
+        );
+    }
+
+    #[test]
+    fn diff_lines() {
+        assert_eq!(diff("a\nb\nc\n", "a\nc\nd\n"), " a\n-b\n c\n+d\n");
+        assert_eq!(diff("", "a\n"), "+a\n");
+        assert_eq!(diff("a\n", ""), "-a\n");
+    }
+
+    #[test]
+    fn assert_writes_new_snapshot() {
+        if accepting() {
+            return;
+        }
+
+        let dir = env::temp_dir().join(format!("aoc_parsed_{}", std::process::id()));
+        let crate_dir = dir.to_str().unwrap();
+
+        let result = std::panic::catch_unwind(|| assert(crate_dir, "c::day01", "x", &[1, 2]));
+        assert!(result.is_err());
+
+        let new_path = path(crate_dir, "c::day01", "x").with_extension("json.new");
+        assert_eq!(fs::read_to_string(&new_path).unwrap(), to_json(&[1, 2]));
+
+        // Accepting the new file makes the test pass
+        fs::rename(&new_path, path(crate_dir, "c::day01", "x")).unwrap();
+        assert(crate_dir, "c::day01", "x", &[1, 2]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,381 @@
+// Parses lines shaped by a pattern into structs, e.g. `move 1 from 2 to 1`
+// with `move {amount} from {src} to {dst}`:
+//
+//     #[derive(FromLine)]
+//     #[pattern("move {amount} from {src} to {dst}")]
+//     struct MoveInstruction {
+//         amount: usize,
+//         src: usize,
+//         dst: usize,
+//     }
+//
+//     let instruction = MoveInstruction::from_line("move 1 from 2 to 1")?;
+//
+// The text around the fields has to match exactly. A field runs up to the
+// first place the text after it is found, the last one up to the end of the
+// line, and spaces around a field are ignored. Fields are parsed with their
+// own type, which can be another struct deriving FromLine.
+//
+// A field written `{name:sep}` is a list split by the separator, where
+// repeated separators count as one, so `{numbers: }` reads `83 86  6` as
+// [83, 86, 6].
+use std::fmt;
+use std::num::IntErrorKind;
+
+use crate::numbers;
+
+pub use aoc_macros::FromLine;
+
+pub trait FromLine: Sized {
+    const PATTERN: &'static str;
+
+    fn from_line(line: &str) -> Result<Self, PatternError>;
+}
+
+// A value read from the text of a single field
+pub trait Field: Sized {
+    fn from_field(text: &str) -> Result<Self, String>;
+}
+
+// Integers are read by `numbers`, so fields and extracted numbers share the
+// same sign and overflow rules.
+macro_rules! integer_field {
+    ($($t:ty),*) => {
+        $(
+            impl Field for $t {
+                fn from_field(text: &str) -> Result<Self, String> {
+                    numbers::whole(text).map_err(|err| {
+                        let reason = match err.kind {
+                            IntErrorKind::PosOverflow => "too big",
+                            IntErrorKind::NegOverflow => "too small",
+                            IntErrorKind::Empty => "empty",
+                            _ => "invalid digit",
+                        };
+                        format!("{text:?} is not a valid {}: {reason}", stringify!($t))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+integer_field!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! from_str_field {
+    ($($t:ty),*) => {
+        $(
+            impl Field for $t {
+                fn from_field(text: &str) -> Result<Self, String> {
+                    text.parse()
+                        .map_err(|err| format!("{text:?} is not a valid {}: {err}", stringify!($t)))
+                }
+            }
+        )*
+    };
+}
+
+from_str_field!(f32, f64, bool, char, String);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatternError {
+    pub line: String,
+    pub pattern: &'static str,
+    // Byte offset of the line where the match failed
+    pub column: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} does not match {:?} at column {}: {}",
+            self.line, self.pattern, self.column, self.reason
+        )
+    }
+}
+
+// The text of every field of a line matched by a pattern, used by the code
+// generated for FromLine.
+pub struct Match<'a> {
+    line: &'a str,
+    pattern: &'static str,
+    // Column and text of every field
+    fields: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Match<'a> {
+    // The literals are the text before, between and after the fields of the
+    // pattern, one more than the fields.
+    pub fn new(
+        line: &'a str,
+        pattern: &'static str,
+        literals: &[&str],
+    ) -> Result<Match<'a>, PatternError> {
+        let mut matched = Match {
+            line,
+            pattern,
+            fields: vec![],
+        };
+        let Some((first, rest)) = literals.split_first() else {
+            return Ok(matched);
+        };
+
+        if !line.starts_with(first) {
+            return Err(matched.error(0, format!("expected {first:?}")));
+        }
+        let mut at = first.len();
+
+        for (i, literal) in rest.iter().enumerate() {
+            let found = if i == rest.len() - 1 {
+                line[at..]
+                    .ends_with(literal)
+                    .then(|| line.len() - literal.len())
+            } else {
+                line[at..].find(literal).map(|pos| at + pos)
+            };
+
+            let Some(end) = found else {
+                return Err(matched.error(at, format!("expected {literal:?}")));
+            };
+            matched.fields.push((at, &line[at..end]));
+            at = end + literal.len();
+        }
+
+        // Only a pattern without fields can stop before the end of the line
+        if at < line.len() {
+            let rest = &line[at..];
+            return Err(matched.error(at, format!("unexpected {rest:?}")));
+        }
+
+        Ok(matched)
+    }
+
+    pub fn field<T: Field>(&self, index: usize, name: &str) -> Result<T, PatternError> {
+        let (column, text) = self.fields[index];
+
+        T::from_field(text.trim()).map_err(|reason| self.error(column, format!("{name}: {reason}")))
+    }
+
+    pub fn list<T: Field>(
+        &self,
+        index: usize,
+        name: &str,
+        separator: &str,
+    ) -> Result<Vec<T>, PatternError> {
+        let (column, text) = self.fields[index];
+
+        text.split(separator)
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .enumerate()
+            .map(|(i, item)| {
+                T::from_field(item)
+                    .map_err(|reason| self.error(column, format!("{name}[{i}]: {reason}")))
+            })
+            .collect()
+    }
+
+    fn error(&self, column: usize, reason: String) -> PatternError {
+        PatternError {
+            line: self.line.to_string(),
+            pattern: self.pattern,
+            column,
+            reason,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, FromLine)]
+    #[pattern("move {amount} from {src} to {dst}")]
+    struct MoveInstruction {
+        amount: usize,
+        src: usize,
+        dst: usize,
+    }
+
+    #[derive(Debug, PartialEq, FromLine)]
+    #[pattern("Card {id}: {winning: } | {numbers: }")]
+    struct Card {
+        id: u32,
+        winning: Vec<u32>,
+        numbers: Vec<u32>,
+    }
+
+    #[derive(Debug, PartialEq, FromLine)]
+    #[pattern("{count} {color}")]
+    struct Cubes {
+        count: usize,
+        color: String,
+    }
+
+    #[derive(Debug, PartialEq, FromLine)]
+    #[pattern("{cubes:, }")]
+    struct Round {
+        cubes: Vec<Cubes>,
+    }
+
+    #[derive(Debug, PartialEq, FromLine)]
+    #[pattern("Game {id}: {rounds:;}")]
+    struct Game {
+        id: usize,
+        rounds: Vec<Round>,
+    }
+
+    #[derive(Debug, PartialEq, FromLine)]
+    #[pattern("{{{name}}} = {value}")]
+    struct Braces {
+        name: char,
+        value: i64,
+    }
+
+    #[test]
+    fn parses_typed_fields() {
+        assert_eq!(
+            MoveInstruction::from_line("move 12 from 2 to 1"),
+            Ok(MoveInstruction {
+                amount: 12,
+                src: 2,
+                dst: 1,
+            })
+        );
+        assert_eq!(
+            Braces::from_line("{x} = -3"),
+            Ok(Braces {
+                name: 'x',
+                value: -3,
+            })
+        );
+    }
+
+    #[test]
+    fn repeated_separators_count_as_one() {
+        assert_eq!(
+            Card::from_line("Card   3:  1 21 | 69  1 "),
+            Ok(Card {
+                id: 3,
+                winning: vec![1, 21],
+                numbers: vec![69, 1],
+            })
+        );
+        assert_eq!(
+            Card::from_line("Card 4:  | "),
+            Ok(Card {
+                id: 4,
+                winning: vec![],
+                numbers: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn parses_nested_patterns() {
+        let cubes = |count, color: &str| Cubes {
+            count,
+            color: color.to_string(),
+        };
+
+        assert_eq!(
+            Game::from_line("Game 1: 3 blue, 4 red; 2 green"),
+            Ok(Game {
+                id: 1,
+                rounds: vec![
+                    Round {
+                        cubes: vec![cubes(3, "blue"), cubes(4, "red")],
+                    },
+                    Round {
+                        cubes: vec![cubes(2, "green")],
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn reports_where_lines_do_not_match() {
+        let err = MoveInstruction::from_line("move 1 from 2").unwrap_err();
+        assert_eq!(err.column, 12);
+        assert_eq!(
+            err.to_string(),
+            r#""move 1 from 2" does not match "move {amount} from {src} to {dst}" at column 12: expected " to ""#
+        );
+
+        let err = MoveInstruction::from_line("push 1 from 2 to 3").unwrap_err();
+        assert_eq!(err.column, 0);
+        assert_eq!(err.reason, r#"expected "move ""#);
+
+        let err = MoveInstruction::from_line("move 1 from x to 3").unwrap_err();
+        assert_eq!(err.column, 12);
+        assert_eq!(
+            err.reason,
+            r#"src: "x" is not a valid usize: invalid digit"#
+        );
+
+        let err = MoveInstruction::from_line("move 1 from 2 to 3 now").unwrap_err();
+        assert_eq!(
+            err.reason,
+            r#"dst: "3 now" is not a valid usize: invalid digit"#
+        );
+
+        let err = Card::from_line("Card 1: 4 -5 | 6").unwrap_err();
+        assert_eq!(err.column, 8);
+        assert_eq!(
+            err.reason,
+            r#"winning[1]: "-5" is not a valid u32: invalid digit"#
+        );
+
+        let err = Card::from_line("Card 1: 4 | 4294967296").unwrap_err();
+        assert_eq!(
+            err.reason,
+            r#"numbers[0]: "4294967296" is not a valid u32: too big"#
+        );
+    }
+
+    #[test]
+    fn nested_errors_keep_the_inner_reason() {
+        let err = Game::from_line("Game 1: 3 blue, red").unwrap_err();
+        assert!(err.reason.starts_with("rounds[0]: "), "{}", err.reason);
+        assert!(
+            err.reason
+                .contains(r#""red" does not match "{count} {color}""#),
+            "{}",
+            err.reason
+        );
+    }
+
+    #[test]
+    fn patterns_without_fields_match_the_whole_line() {
+        #[derive(Debug, PartialEq, FromLine)]
+        #[pattern("noop")]
+        struct Noop {}
+
+        assert_eq!(Noop::from_line("noop"), Ok(Noop {}));
+
+        let err = Noop::from_line("noop 3").unwrap_err();
+        assert_eq!(err.column, 4);
+        assert_eq!(err.reason, r#"unexpected " 3""#);
+    }
+
+    #[test]
+    fn last_literal_matches_the_end_of_the_line() {
+        #[derive(Debug, PartialEq, FromLine)]
+        #[pattern("<{inner}>")]
+        struct Tag {
+            inner: String,
+        }
+
+        assert_eq!(
+            Tag::from_line("<a>b>"),
+            Ok(Tag {
+                inner: "a>b".to_string(),
+            })
+        );
+        assert_eq!(Tag::from_line("<>").map(|t| t.inner), Ok("".to_string()));
+        assert_eq!(Tag::from_line(">").unwrap_err().reason, r#"expected "<""#);
+        assert_eq!(Tag::from_line("<a").unwrap_err().column, 1);
+    }
+}
@@ -0,0 +1,269 @@
+// Differential testing of the solutions against slow but obviously correct
+// oracles. Both run over many generated inputs and any disagreement is shrunk
+// to a minimal failing input by removing lines and tokens, as long as the
+// oracle still accepts the input as valid.
+use std::any::Any;
+use std::env;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::solution::Registry;
+
+// Reference solver of a part, returning None when the input is not valid
+// for the puzzle.
+pub type Oracle = fn(&str) -> Option<String>;
+
+pub struct Config {
+    pub cases: usize,
+    pub max_size: usize,
+    pub seed: u64,
+}
+
+impl Config {
+    // Runs 1000 cases by default, `AOC_PROPERTY_CASES` and `AOC_PROPERTY_SEED`
+    // allow longer runs or replaying other seeds.
+    pub fn new(max_size: usize) -> Config {
+        let var = |name: &str| env::var(name).ok().and_then(|v| v.parse().ok());
+
+        Config {
+            cases: var("AOC_PROPERTY_CASES").unwrap_or(1000) as usize,
+            max_size: max_size.max(1),
+            seed: var("AOC_PROPERTY_SEED").unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Failure {
+    pub seed: u64,
+    pub size: usize,
+    // Shrunk input still making the solution disagree with the oracle
+    pub input: String,
+    pub expected: String,
+    pub actual: Result<String, String>,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let actual = match &self.actual {
+            Ok(answer) => answer.clone(),
+            Err(err) => format!("error {}", err),
+        };
+
+        write!(
+            f,
+            "solution disagrees with the oracle (seed={} size={})\nexpected: {}\nactual: {}\nminimal input:\n{}",
+            self.seed, self.size, self.expected, actual, self.input
+        )
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => format!("panicked: {}", message),
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => format!("panicked: {}", message),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+// The expected and actual answers when the solution does not agree with the
+// oracle on a valid input.
+fn disagreement<S>(
+    input: &str,
+    oracle: Oracle,
+    solution: &S,
+) -> Option<(String, Result<String, String>)>
+where
+    S: Fn(&str) -> Result<String, String>,
+{
+    let expected = panic::catch_unwind(|| oracle(input)).ok().flatten()?;
+
+    let actual = panic::catch_unwind(AssertUnwindSafe(|| solution(input)))
+        .unwrap_or_else(|payload| Err(panic_message(payload)));
+
+    match actual {
+        Ok(answer) if answer == expected => None,
+        actual => Some((expected, actual)),
+    }
+}
+
+fn join(lines: &[String]) -> String {
+    lines.iter().map(|l| format!("{}\n", l)).collect()
+}
+
+// Removes chunks of lines, then single tokens of each line, while the input
+// keeps failing.
+pub fn shrink<F: Fn(&str) -> bool>(input: &str, fails: F) -> String {
+    let mut lines: Vec<String> = input.lines().map(|l| l.to_string()).collect();
+
+    let mut chunk = lines.len().div_ceil(2).max(1);
+    loop {
+        let mut removed = false;
+        let mut i = 0;
+
+        while i < lines.len() {
+            let mut candidate = lines.clone();
+            candidate.drain(i..(i + chunk).min(lines.len()));
+
+            if fails(&join(&candidate)) {
+                lines = candidate;
+                removed = true;
+            } else {
+                i += chunk;
+            }
+        }
+
+        if chunk == 1 && !removed {
+            break;
+        }
+        chunk = (chunk / 2).max(1);
+    }
+
+    for l in 0..lines.len() {
+        let mut t = 0;
+
+        while !lines[l].is_empty() && t < lines[l].split(' ').count() {
+            let mut tokens: Vec<&str> = lines[l].split(' ').collect();
+            tokens.remove(t);
+
+            let mut candidate = lines.clone();
+            candidate[l] = tokens.join(" ");
+
+            if fails(&join(&candidate)) {
+                lines = candidate;
+            } else {
+                t += 1;
+            }
+        }
+    }
+
+    join(&lines)
+}
+
+// Runs the solution and the oracle over generated inputs of growing size.
+pub fn check<S>(
+    config: &Config,
+    generate: fn(u64, usize) -> String,
+    oracle: Oracle,
+    solution: S,
+) -> Result<(), Failure>
+where
+    S: Fn(&str) -> Result<String, String>,
+{
+    for case in 0..config.cases {
+        let seed = config.seed + case as u64;
+        let size = 1 + case % config.max_size;
+        let input = generate(seed, size);
+
+        if disagreement(&input, oracle, &solution).is_none() {
+            continue;
+        }
+
+        let input = shrink(&input, |i| disagreement(i, oracle, &solution).is_some());
+        let (expected, actual) =
+            disagreement(&input, oracle, &solution).expect("Shrunk input passes");
+
+        return Err(Failure {
+            seed,
+            size,
+            input,
+            expected,
+            actual,
+        });
+    }
+
+    Ok(())
+}
+
+// Checks every registered solution of a day against the oracle of its part,
+// using the day input generator. Panics with the minimal failing input.
+pub fn assert_day(registry: &Registry, day: u8, oracles: &[Oracle], max_size: usize) {
+    let generator = registry.generator(day).expect("Missing input generator");
+    let config = Config::new(max_size);
+
+    for solution in registry.solutions.iter().filter(|s| s.day == day) {
+        let oracle = oracles[solution.part as usize - 1];
+
+        if let Err(failure) = check(&config, generator.generate, oracle, |input| {
+            solution.process(input)
+        }) {
+            panic!("{} {}: {}", registry.year, solution.name(), failure);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(seed: u64, size: usize) -> String {
+        (0..size).map(|n| format!("{} {}\n", seed, n)).collect()
+    }
+
+    // Sum of every number in the input
+    fn oracle(input: &str) -> Option<String> {
+        let mut total: u64 = 0;
+        for token in input.split_whitespace() {
+            total += token.parse::<u64>().ok()?;
+        }
+        Some(total.to_string())
+    }
+
+    #[test]
+    fn check_passes() {
+        let config = Config {
+            cases: 100,
+            max_size: 10,
+            seed: 0,
+        };
+
+        assert_eq!(
+            check(&config, generate, oracle, |i| Ok(oracle(i).unwrap())),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_shrinks_failure() {
+        let config = Config {
+            cases: 100,
+            max_size: 10,
+            seed: 0,
+        };
+
+        // Wrong when any number is 7
+        let solution = |input: &str| {
+            if input.split_whitespace().any(|t| t == "7") {
+                return Err("found 7".to_string());
+            }
+            Ok(oracle(input).unwrap())
+        };
+
+        let failure = check(&config, generate, oracle, solution).unwrap_err();
+        assert_eq!(failure.input, "7\n");
+        assert_eq!(failure.expected, "7");
+        assert_eq!(failure.actual, Err("found 7".to_string()));
+    }
+
+    #[test]
+    fn check_catches_panics() {
+        let config = Config {
+            cases: 10,
+            max_size: 3,
+            seed: 0,
+        };
+
+        let solution = |input: &str| -> Result<String, String> {
+            if input.lines().count() > 1 {
+                panic!("too many lines");
+            }
+            Ok(oracle(input).unwrap())
+        };
+
+        let failure = check(&config, generate, oracle, solution).unwrap_err();
+        assert_eq!(failure.input.lines().count(), 2);
+        assert_eq!(failure.actual, Err("panicked: too many lines".to_string()));
+    }
+}
@@ -0,0 +1,369 @@
+use clap::Args;
+use serde::{Serialize, Serializer};
+#[cfg(unix)]
+use std::env;
+#[cfg(unix)]
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::alloc::Memory;
+use crate::format::{self, Format};
+use crate::input;
+#[cfg(unix)]
+use crate::isolate;
+use crate::log::{self, Filter};
+use crate::output::{ColorChoice, Output, State};
+use crate::solution::{self, Budget, Cancel, Registry, Run, Solution};
+
+// Options shared by every runner command line.
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    /// Output format of the solution results
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// When to use colors in the text output
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Enable the solutions logs (e.g. 2022::day05=trace,2023=debug)
+    #[arg(long, value_parser = Filter::parse)]
+    pub log: Option<Filter>,
+
+    /// Run every solution in its own process with memory and CPU limits
+    #[cfg(unix)]
+    #[arg(long)]
+    pub isolate: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    // The answer matches the known answer
+    Passed,
+    // The answer does not match the known answer
+    Failed,
+    // There is no known answer to compare with
+    Unknown,
+    // The solution could not produce an answer
+    Error,
+}
+
+impl Status {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Passed => "passed",
+            Status::Failed => "failed",
+            Status::Unknown => "unknown",
+            Status::Error => "error",
+        }
+    }
+}
+
+// Part of the budget of a solution a run went past
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverBudget {
+    // Took longer than the time budget
+    Time,
+    // Used more memory than the ceiling, or was cancelled for it
+    Memory,
+    // Was cancelled at the timeout
+    Timeout,
+}
+
+// How an isolated run failed without giving an answer
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Crash {
+    Panic { message: String },
+    // Could not allocate within the memory ceiling
+    OutOfMemory,
+    // Used up its CPU time limit
+    CpuLimit,
+    // Still running at the timeout and killed
+    Timeout,
+    Signal { signal: i32 },
+    Exit { code: i32 },
+    // The child process could not be started
+    Spawn { error: String },
+}
+
+impl Crash {
+    // Kind of the crash as serialized, e.g. `out_of_memory`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Crash::Panic { .. } => "panic",
+            Crash::OutOfMemory => "out_of_memory",
+            Crash::CpuLimit => "cpu_limit",
+            Crash::Timeout => "timeout",
+            Crash::Signal { .. } => "signal",
+            Crash::Exit { .. } => "exit",
+            Crash::Spawn { .. } => "spawn",
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Crash::Panic { message } => format!("panicked: {}", message),
+            Crash::OutOfMemory => "ran out of memory".to_string(),
+            Crash::CpuLimit => "went past its CPU time limit".to_string(),
+            Crash::Timeout => "killed at the timeout".to_string(),
+            Crash::Signal { signal } => format!("killed by signal {}", signal),
+            Crash::Exit { code } => format!("exited with code {} without an answer", code),
+            Crash::Spawn { error } => format!("could not start isolated run: {}", error),
+        }
+    }
+}
+
+// The result of running a single solution against one of its inputs.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub version: u8,
+    pub demo: bool,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub status: Status,
+    #[serde(rename = "duration_ms", serialize_with = "serialize_millis")]
+    pub duration: Duration,
+    // Split of the duration, the parse time is None for solutions without
+    // a parse step
+    #[serde(rename = "parse_ms", serialize_with = "serialize_optional_millis")]
+    pub parse_duration: Option<Duration>,
+    #[serde(rename = "solve_ms", serialize_with = "serialize_millis")]
+    pub solve_duration: Duration,
+    // Heap usage, only measured with the `alloc-stats` feature
+    pub memory: Option<Memory>,
+    #[serde(skip)]
+    pub budget: Budget,
+    pub over_budget: Option<OverBudget>,
+    // Measured while a cancelled run was still going in the background, so
+    // the time is skewed and the memory is not reported
+    pub unreliable: bool,
+    // How an isolated run failed, only with `--isolate`
+    pub crash: Option<Crash>,
+    pub error: Option<String>,
+}
+
+fn serialize_millis<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+fn serialize_optional_millis<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serialize_millis(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+// Why a run stopped without finishing
+enum Stop {
+    Cancel(Cancel),
+    Crash(Crash),
+}
+
+pub fn run_solution(registry: &Registry, solution: &Solution, demo: bool) -> Record {
+    run_solution_with(registry, solution, demo, false)
+}
+
+// Runs the solution in a child process of the same command line, see
+// `isolate`.
+#[cfg(unix)]
+fn run_isolated(solution: &Solution, input: &str) -> Result<Run, Crash> {
+    let exe = env::current_exe().map_err(|err| Crash::Spawn {
+        error: err.to_string(),
+    })?;
+    let mut command = Command::new(exe);
+    command.args(env::args_os().skip(1));
+
+    isolate::run(command, solution, input)
+}
+
+#[cfg(not(unix))]
+fn run_isolated(_solution: &Solution, _input: &str) -> Result<Run, Crash> {
+    Err(Crash::Spawn {
+        error: "isolated runs are only supported on unix".to_string(),
+    })
+}
+
+pub fn run_solution_with(
+    registry: &Registry,
+    solution: &Solution,
+    demo: bool,
+    isolated: bool,
+) -> Record {
+    let mut record = Record {
+        year: solution.year,
+        day: solution.day,
+        part: solution.part,
+        version: solution.version,
+        demo,
+        answer: None,
+        expected: None,
+        status: Status::Error,
+        duration: Duration::ZERO,
+        parse_duration: None,
+        solve_duration: Duration::ZERO,
+        memory: None,
+        budget: solution.budget,
+        over_budget: None,
+        unreliable: false,
+        crash: None,
+        error: None,
+    };
+
+    let input = match input::load(registry.src_dir, solution.day, solution.part, demo) {
+        Ok(input) => input,
+        Err(err) => {
+            record.error = Some(format!("could not read input: {}", err));
+            return record;
+        }
+    };
+
+    record.expected = registry
+        .answers
+        .expected(solution.day, solution.part, demo, &input)
+        .map(|e| e.to_string());
+
+    let run = match isolated {
+        true => run_isolated(solution, &input).map_err(Stop::Crash),
+        false => {
+            record.unreliable = solution::abandoned_runs() > 0;
+            solution.run_budgeted(&input).map_err(Stop::Cancel)
+        }
+    };
+
+    let run = match run {
+        Ok(run) => run,
+        Err(Stop::Crash(Crash::Timeout)) => {
+            record.duration = solution.budget.timeout();
+            record.solve_duration = record.duration;
+            record.over_budget = Some(OverBudget::Timeout);
+            record.error = Some(format!("killed after {:?}", solution.budget.timeout()));
+            record.crash = Some(Crash::Timeout);
+            return record;
+        }
+        Err(Stop::Crash(crash)) => {
+            if crash == Crash::OutOfMemory {
+                record.over_budget = Some(OverBudget::Memory);
+            }
+            record.error = Some(crash.describe());
+            record.crash = Some(crash);
+            return record;
+        }
+        Err(Stop::Cancel(Cancel::Timeout)) => {
+            record.duration = solution.budget.timeout();
+            record.solve_duration = record.duration;
+            record.over_budget = Some(OverBudget::Timeout);
+            record.error = Some(format!("cancelled after {:?}", solution.budget.timeout()));
+            return record;
+        }
+        Err(Stop::Cancel(Cancel::Memory)) => {
+            record.over_budget = Some(OverBudget::Memory);
+            record.error = Some(format!(
+                "cancelled over {} of memory",
+                format::bytes(solution.budget.memory)
+            ));
+            return record;
+        }
+    };
+
+    record.duration = run.timing.total();
+    record.parse_duration = run.timing.parse;
+    record.solve_duration = run.timing.solve;
+    record.memory = run.memory.filter(|_| !record.unreliable);
+
+    if record.duration > solution.budget.time {
+        record.over_budget = Some(OverBudget::Time);
+    } else if record
+        .memory
+        .is_some_and(|m| m.peak_bytes > solution.budget.memory)
+    {
+        record.over_budget = Some(OverBudget::Memory);
+    }
+
+    match run.result {
+        Ok(answer) => {
+            record.status = match &record.expected {
+                Some(expected) if *expected == answer => Status::Passed,
+                Some(_) => Status::Failed,
+                None => Status::Unknown,
+            };
+            record.answer = Some(answer);
+        }
+        Err(err) => record.error = Some(err),
+    }
+
+    record
+}
+
+// Answers as the isolated child process when the runner started this one
+// for a solution, exiting once done. Must be called before anything is
+// printed.
+pub fn serve(registry: &Registry, args: &RunArgs) {
+    if let Some(filter) = &args.log {
+        log::init(filter.clone());
+    }
+
+    #[cfg(unix)]
+    isolate::serve(registry);
+    #[cfg(not(unix))]
+    let _ = registry;
+}
+
+// Runs the solutions printing the results in the requested format.
+// Returns false if any solution failed or could not produce an answer.
+pub fn run(registry: &Registry, solutions: &[&Solution], demo: bool, args: &RunArgs) -> bool {
+    if let Some(filter) = &args.log {
+        log::init(filter.clone());
+    }
+
+    let output = Output::new(args.color);
+    #[cfg(unix)]
+    let isolated = args.isolate;
+    #[cfg(not(unix))]
+    let isolated = false;
+    let mut records = vec![];
+    let start = Instant::now();
+
+    for s in solutions {
+        if args.format == Format::Text {
+            println!(
+                "\n{} Running year={} day={} part={} version={} demo={}",
+                output.icon(State::Running),
+                s.year,
+                s.day,
+                s.part,
+                s.version,
+                demo
+            );
+        }
+
+        let record = run_solution_with(registry, s, demo, isolated);
+
+        if args.format == Format::Text {
+            println!("{}", format::text(&record, &output));
+        }
+
+        records.push(record);
+    }
+
+    if args.format == Format::Text {
+        print!(
+            "\n{}",
+            format::summary(registry.year, &records, start.elapsed(), &output)
+        );
+    } else {
+        print!("{}", format::render(args.format, &records));
+    }
+
+    records
+        .iter()
+        .all(|r| r.status == Status::Passed || r.status == Status::Unknown)
+}
@@ -0,0 +1,125 @@
+// Snapshot of the current answers of every solution of a year, kept in
+// `snapshot.toml` next to `answers.toml`. Unlike the answers store, it has an
+// entry for every solution version, whether its answer is known or not, so
+// refactors can be checked for any changed result.
+//
+//   [day02_part01_v2.real]
+//   answer = "13565"
+//   input_hash = "588911b4b5a87924"
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::answers::{input_kind, Entry};
+use crate::solution::{Registry, Solution};
+
+// Layout of the TOML file: solution name -> demo|real -> entry
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    entries: BTreeMap<String, BTreeMap<String, Entry>>,
+}
+
+impl Snapshot {
+    pub fn parse(text: &str) -> Result<Snapshot, String> {
+        let entries = toml::from_str(text).map_err(|err| err.to_string())?;
+        Ok(Snapshot { entries })
+    }
+
+    // Loads the snapshot, an empty one when it was never recorded.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Snapshot, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Snapshot::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Snapshot::default()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(&self.entries).expect("Could not serialize snapshot")
+    }
+
+    pub fn get(&self, solution: &Solution, demo: bool) -> Option<&Entry> {
+        self.entries
+            .get(&solution.name())
+            .and_then(|inputs| inputs.get(input_kind(demo)))
+    }
+
+    pub fn insert(&mut self, solution: &Solution, demo: bool, entry: Entry) {
+        self.entries
+            .entry(solution.name())
+            .or_default()
+            .insert(input_kind(demo).to_string(), entry);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.values().map(|inputs| inputs.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// The snapshot lives in the year crate directory, the parent of `src`.
+pub fn path(registry: &Registry) -> PathBuf {
+    let src_dir = Path::new(registry.src_dir);
+    src_dir.parent().unwrap_or(src_dir).join("snapshot.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_round_trip() {
+        let v1 = Solution::new(2022, 2, 1, 1, |_| Ok("".to_string()));
+        let v2 = Solution::new(2022, 2, 1, 2, |_| Ok("".to_string()));
+
+        let mut snapshot = Snapshot::default();
+        snapshot.insert(
+            &v2,
+            false,
+            Entry {
+                answer: "13052".to_string(),
+                input_hash: "0000000000000001".to_string(),
+                ignore: None,
+                ignore_versions: None,
+            },
+        );
+
+        let text = snapshot.to_toml();
+        assert_eq!(
+            text,
+            "[day02_part01_v2.real]\nanswer = \"13052\"\ninput_hash = \"0000000000000001\"\n"
+        );
+
+        let snapshot = Snapshot::parse(&text).unwrap();
+        assert_eq!(snapshot.get(&v2, false).unwrap().answer, "13052");
+        assert_eq!(snapshot.get(&v2, true), None);
+        assert_eq!(snapshot.get(&v1, false), None);
+        assert_eq!(snapshot.len(), 1);
+    }
+
+    #[test]
+    fn snapshot_path() {
+        let registry = Registry {
+            year: 2022,
+            src_dir: "/code/aoc_2022_rust/src",
+            solutions: vec![],
+            answers: Default::default(),
+            generators: vec![],
+        };
+
+        assert_eq!(
+            path(&registry),
+            PathBuf::from("/code/aoc_2022_rust/snapshot.toml")
+        );
+    }
+
+    #[test]
+    fn load_missing_snapshot() {
+        let snapshot = Snapshot::load("/nonexistent/snapshot.toml").unwrap();
+        assert!(snapshot.is_empty());
+    }
+}
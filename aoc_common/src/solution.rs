@@ -0,0 +1,374 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::alloc::{self, Memory};
+use crate::answers::Answers;
+
+// Time spent in each phase of a run, measured with a monotonic clock. The
+// parse time is only known for solutions registered with a parse step,
+// others count all their time as solving.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timing {
+    pub parse: Option<Duration>,
+    pub solve: Duration,
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.parse.unwrap_or_default() + self.solve
+    }
+}
+
+pub struct Run {
+    pub result: Result<String, String>,
+    pub timing: Timing,
+    // Only measured with the counting allocator enabled
+    pub memory: Option<Memory>,
+}
+
+// Limits a solution is expected to run within. Going past the time budget or
+// the memory ceiling marks the run as over budget, and a run still going at
+// the timeout, ten times the time budget, is cancelled. The memory is only
+// checked with the counting allocator enabled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Budget {
+    pub time: Duration,
+    pub memory: usize,
+}
+
+impl Budget {
+    pub fn timeout(&self) -> Duration {
+        self.time * 10
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Budget {
+        Budget {
+            time: Duration::from_secs(1),
+            memory: 1 << 30,
+        }
+    }
+}
+
+// Why a run was given up on
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cancel {
+    Timeout,
+    Memory,
+}
+
+type Process = Arc<dyn Fn(&str) -> (Result<String, String>, Timing) + Send + Sync>;
+
+// How often a budgeted run is checked while waiting for it
+const POLL: Duration = Duration::from_millis(10);
+
+// Cancelled runs still going in the background. They keep using the CPU and
+// the allocation counters shared by all threads, so nothing measured while
+// any is left is reliable.
+static ABANDONED: AtomicUsize = AtomicUsize::new(0);
+
+pub fn abandoned_runs() -> usize {
+    ABANDONED.load(Ordering::SeqCst)
+}
+
+fn execute(process: &Process, input: &str) -> Run {
+    let ((result, timing), memory) = alloc::measure(|| process(input));
+
+    Run {
+        result,
+        timing,
+        memory,
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => format!("panicked: {}", message),
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => format!("panicked: {}", message),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+// A registered puzzle solution. The process receives the whole puzzle input
+// and returns the answer as text, so solutions from every year can be run
+// and reported the same way.
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub version: u8,
+    pub budget: Budget,
+    internal_process: Process,
+}
+
+impl Solution {
+    pub fn new<F>(year: u16, day: u8, part: u8, version: u8, internal_process: F) -> Solution
+    where
+        F: Fn(&str) -> Result<String, String> + Send + Sync + 'static,
+    {
+        Solution {
+            year,
+            day,
+            part,
+            version,
+            budget: Budget::default(),
+            internal_process: Arc::new(move |input| {
+                let start = Instant::now();
+                let result = internal_process(input);
+
+                let timing = Timing {
+                    parse: None,
+                    solve: start.elapsed(),
+                };
+                (result, timing)
+            }),
+        }
+    }
+
+    // A solution split into parsing the input and solving the puzzle from
+    // the parsed data, so both phases are timed on their own.
+    pub fn with_parse<T, P, S>(
+        year: u16,
+        day: u8,
+        part: u8,
+        version: u8,
+        parse: P,
+        solve: S,
+    ) -> Solution
+    where
+        P: Fn(&str) -> Result<T, String> + Send + Sync + 'static,
+        S: Fn(T) -> Result<String, String> + Send + Sync + 'static,
+    {
+        Solution {
+            year,
+            day,
+            part,
+            version,
+            budget: Budget::default(),
+            internal_process: Arc::new(move |input| {
+                let start = Instant::now();
+                let parsed = parse(input);
+                let parse_time = start.elapsed();
+
+                let start = Instant::now();
+                let result = parsed.and_then(&solve);
+
+                let timing = Timing {
+                    parse: Some(parse_time),
+                    solve: start.elapsed(),
+                };
+                (result, timing)
+            }),
+        }
+    }
+
+    pub fn with_budget(mut self, budget: Budget) -> Solution {
+        self.budget = budget;
+        self
+    }
+
+    // Puzzle name as used in the command line (e.g. day01_part01 or day02_part01_v2)
+    pub fn name(&self) -> String {
+        let name = format!("day{:02}_part{:02}", self.day, self.part);
+
+        match self.version {
+            1 => name,
+            v => format!("{}_v{}", name, v),
+        }
+    }
+
+    // Source file of the solution relative to the year `src` directory
+    pub fn source(&self) -> String {
+        match self.version {
+            1 => format!("day{:02}/part{:02}.rs", self.day, self.part),
+            v => format!("day{:02}/part{:02}_v{}.rs", self.day, self.part, v),
+        }
+    }
+
+    pub fn run(&self, input: &str) -> Run {
+        execute(&self.internal_process, input)
+    }
+
+    // Runs the solution in its own thread, giving up on it at the timeout of
+    // its budget or, with the counting allocator, past its memory ceiling. A
+    // panic is returned as an error. Threads can not be stopped, so a
+    // cancelled run keeps going in the background until it finishes, counted
+    // by `abandoned_runs`. The memory is not checked while one is going.
+    pub fn run_budgeted(&self, input: &str) -> Result<Run, Cancel> {
+        let process = self.internal_process.clone();
+        let input = input.to_string();
+        let (sender, receiver) = mpsc::channel();
+        // Set by whichever of the run finishing or the wait giving up is first
+        let finished = Arc::new(AtomicBool::new(false));
+        let thread_finished = finished.clone();
+
+        let in_use = alloc::in_use();
+        let deadline = Instant::now() + self.budget.timeout();
+
+        thread::spawn(move || {
+            let start = Instant::now();
+            let run = panic::catch_unwind(AssertUnwindSafe(|| execute(&process, &input)))
+                .unwrap_or_else(|payload| Run {
+                    result: Err(panic_message(payload)),
+                    timing: Timing {
+                        parse: None,
+                        solve: start.elapsed(),
+                    },
+                    memory: None,
+                });
+            let _ = sender.send(run);
+            if thread_finished.swap(true, Ordering::SeqCst) {
+                ABANDONED.fetch_sub(1, Ordering::SeqCst);
+            }
+        });
+
+        let cancel = loop {
+            match receiver.recv_timeout(POLL) {
+                Ok(run) => return Ok(run),
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Err(Cancel::Timeout),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+            }
+
+            if Instant::now() >= deadline {
+                break Cancel::Timeout;
+            }
+            if alloc::enabled()
+                && abandoned_runs() == 0
+                && alloc::in_use().saturating_sub(in_use) > self.budget.memory
+            {
+                break Cancel::Memory;
+            }
+        };
+
+        ABANDONED.fetch_add(1, Ordering::SeqCst);
+        if finished.swap(true, Ordering::SeqCst) {
+            // The run finished right before giving up on it
+            ABANDONED.fetch_sub(1, Ordering::SeqCst);
+            return receiver.recv().map_err(|_| cancel);
+        }
+
+        Err(cancel)
+    }
+
+    pub fn process(&self, input: &str) -> Result<String, String> {
+        self.run(input).result
+    }
+}
+
+// Builds a random input in the format of a day puzzle. The size is the
+// amount of lines, groups or ranges, depending on the puzzle.
+pub struct Generator {
+    pub day: u8,
+    pub generate: fn(seed: u64, size: usize) -> String,
+}
+
+// All the solutions of a year along with where to find their inputs.
+pub struct Registry {
+    pub year: u16,
+    // Directory containing the `dayXX` folders with the input files
+    pub src_dir: &'static str,
+    pub solutions: Vec<Solution>,
+    pub answers: Answers,
+    pub generators: Vec<Generator>,
+}
+
+impl Registry {
+    pub fn find(&self, day: u8, part: u8, version: u8) -> Option<&Solution> {
+        self.solutions
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.version == version)
+    }
+
+    pub fn generator(&self, day: u8) -> Option<&Generator> {
+        self.generators.iter().find(|g| g.day == day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solution_name() {
+        let s = Solution::new(2022, 1, 2, 1, |_| Ok("".to_string()));
+        assert_eq!(s.name(), "day01_part02");
+
+        let s = Solution::new(2022, 2, 1, 2, |_| Ok("".to_string()));
+        assert_eq!(s.name(), "day02_part01_v2");
+    }
+
+    #[test]
+    fn solution_source() {
+        let s = Solution::new(2022, 1, 2, 1, |_| Ok("".to_string()));
+        assert_eq!(s.source(), "day01/part02.rs");
+
+        let s = Solution::new(2022, 2, 1, 2, |_| Ok("".to_string()));
+        assert_eq!(s.source(), "day02/part01_v2.rs");
+    }
+
+    #[test]
+    fn solution_timing() {
+        let s = Solution::new(2022, 1, 1, 1, |input| Ok(input.to_string()));
+        let run = s.run("1");
+        assert_eq!(run.result, Ok("1".to_string()));
+        assert_eq!(run.timing.parse, None);
+        assert_eq!(run.timing.total(), run.timing.solve);
+
+        let s = Solution::with_parse(
+            2022,
+            1,
+            1,
+            1,
+            |input| input.parse::<u32>().map_err(|err| err.to_string()),
+            |n| Ok((n * 2).to_string()),
+        );
+        let run = s.run("21");
+        assert_eq!(run.result, Ok("42".to_string()));
+        assert!(run.timing.parse.is_some());
+        assert_eq!(
+            run.timing.total(),
+            run.timing.parse.unwrap() + run.timing.solve
+        );
+
+        assert!(s.process("x").is_err());
+    }
+
+    #[test]
+    fn solution_budget() {
+        let s = Solution::new(2022, 1, 1, 1, |input| Ok(input.to_string()));
+        assert_eq!(s.budget, Budget::default());
+        assert_eq!(s.run_budgeted("1").unwrap().result, Ok("1".to_string()));
+
+        let s = Solution::new(2022, 1, 1, 1, |_| panic!("boom"));
+        assert_eq!(
+            s.run_budgeted("").unwrap().result,
+            Err("panicked: boom".to_string())
+        );
+
+        let s = Solution::new(2022, 1, 1, 1, |_| {
+            thread::sleep(Duration::from_millis(500));
+            Ok("".to_string())
+        })
+        .with_budget(Budget {
+            time: Duration::from_millis(5),
+            memory: 0,
+        });
+
+        let start = Instant::now();
+        assert_eq!(s.run_budgeted("").err(), Some(Cancel::Timeout));
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert!(abandoned_runs() > 0);
+
+        // The count goes back down once the cancelled run finishes
+        while abandoned_runs() > 0 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
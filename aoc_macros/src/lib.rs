@@ -0,0 +1,280 @@
+// Derive macros shared by the year crates.
+//
+// `#[derive(FromLine)]` fills a struct from a line shaped by the pattern of
+// its `#[pattern("...")]` attribute, e.g.
+//
+//     #[derive(FromLine)]
+//     #[pattern("move {amount} from {src} to {dst}")]
+//     struct MoveInstruction {
+//         amount: usize,
+//         src: usize,
+//         dst: usize,
+//     }
+//
+// Every field of the struct has to appear once in the pattern and is parsed
+// with its own type. `{name:sep}` is a list split by the separator, `{{` and
+// `}}` are literal braces. The generated code uses `aoc_common::pattern`,
+// where the matching rules are described.
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+#[proc_macro_derive(FromLine, attributes(pattern))]
+pub fn derive_from_line(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let attr = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("pattern"))
+        .ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                "FromLine needs a #[pattern(\"...\")] attribute",
+            )
+        })?;
+    let lit: LitStr = attr.parse_args()?;
+    let pattern = Pattern::parse(&lit.value()).map_err(|msg| syn::Error::new(lit.span(), msg))?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "FromLine only supports structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "FromLine only supports structs with named fields",
+        ));
+    };
+
+    let struct_fields: Vec<String> = named
+        .named
+        .iter()
+        .filter_map(|field| field.ident.as_ref().map(|ident| ident.to_string()))
+        .collect();
+    for field in &pattern.fields {
+        if !struct_fields.contains(&field.name) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!(
+                    "the pattern field {{{}}} is not a field of the struct",
+                    field.name
+                ),
+            ));
+        }
+    }
+    for name in &struct_fields {
+        if !pattern.fields.iter().any(|field| &field.name == name) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("the field {name} is missing from the pattern"),
+            ));
+        }
+    }
+
+    let literals = &pattern.literals;
+    let values = pattern.fields.iter().enumerate().map(|(index, field)| {
+        let ident = syn::Ident::new(&field.name, lit.span());
+        let name = &field.name;
+        match &field.separator {
+            Some(separator) => quote! { #ident: matched.list(#index, #name, #separator)? },
+            None => quote! { #ident: matched.field(#index, #name)? },
+        }
+    });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::aoc_common::pattern::FromLine for #ident #ty_generics #where_clause {
+            const PATTERN: &'static str = #lit;
+
+            fn from_line(line: &str) -> ::std::result::Result<Self, ::aoc_common::pattern::PatternError> {
+                let matched = ::aoc_common::pattern::Match::new(line, Self::PATTERN, &[#(#literals),*])?;
+
+                Ok(Self {
+                    #(#values),*
+                })
+            }
+        }
+
+        impl #impl_generics ::aoc_common::pattern::Field for #ident #ty_generics #where_clause {
+            fn from_field(text: &str) -> ::std::result::Result<Self, ::std::string::String> {
+                <Self as ::aoc_common::pattern::FromLine>::from_line(text).map_err(|err| err.to_string())
+            }
+        }
+    })
+}
+
+#[derive(Debug, PartialEq)]
+struct PatternField {
+    name: String,
+    separator: Option<String>,
+}
+
+// The literals are the text before, between and after the fields, so there is
+// always one more literal than fields.
+#[derive(Debug, PartialEq)]
+struct Pattern {
+    literals: Vec<String>,
+    fields: Vec<PatternField>,
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Result<Pattern, String> {
+        let mut literals = vec![];
+        let mut fields: Vec<PatternField> = vec![];
+        let mut literal = String::new();
+        let mut chars = pattern.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return Err("unmatched } in the pattern, use }} for a literal }".into()),
+                '{' => {
+                    let mut spec = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => spec.push(c),
+                            None => return Err(format!("unclosed {{{spec} in the pattern")),
+                        }
+                    }
+
+                    let field = parse_field(&spec)?;
+                    if let Some(previous) = fields.last() {
+                        if literal.is_empty() {
+                            return Err(format!(
+                                "the fields {{{}}} and {{{}}} need some text between them",
+                                previous.name, field.name
+                            ));
+                        }
+                    }
+                    if fields.iter().any(|f| f.name == field.name) {
+                        return Err(format!("the field {{{}}} is used twice", field.name));
+                    }
+
+                    literals.push(std::mem::take(&mut literal));
+                    fields.push(field);
+                }
+                c => literal.push(c),
+            }
+        }
+        literals.push(literal);
+
+        Ok(Pattern { literals, fields })
+    }
+}
+
+// Parses the `name` or `name:separator` inside the braces of a field
+fn parse_field(spec: &str) -> Result<PatternField, String> {
+    let (name, separator) = match spec.split_once(':') {
+        Some((name, separator)) => (name, Some(separator)),
+        None => (spec, None),
+    };
+
+    let name = name.trim();
+    let valid = name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!("{{{spec}}} does not start with a field name"));
+    }
+    if separator == Some("") {
+        return Err(format!("the list {{{spec}}} has an empty separator"));
+    }
+
+    Ok(PatternField {
+        name: name.to_string(),
+        separator: separator.map(|s| s.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, separator: Option<&str>) -> PatternField {
+        PatternField {
+            name: name.to_string(),
+            separator: separator.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn parses_patterns() {
+        assert_eq!(
+            Pattern::parse("move {amount} from {src} to {dst}"),
+            Ok(Pattern {
+                literals: vec!["move ".into(), " from ".into(), " to ".into(), "".into()],
+                fields: vec![
+                    field("amount", None),
+                    field("src", None),
+                    field("dst", None)
+                ],
+            })
+        );
+        assert_eq!(
+            Pattern::parse("Card {id}: {winning: } | {numbers: }"),
+            Ok(Pattern {
+                literals: vec!["Card ".into(), ": ".into(), " | ".into(), "".into()],
+                fields: vec![
+                    field("id", None),
+                    field("winning", Some(" ")),
+                    field("numbers", Some(" ")),
+                ],
+            })
+        );
+        assert_eq!(
+            Pattern::parse("{{{x}}}"),
+            Ok(Pattern {
+                literals: vec!["{".into(), "}".into()],
+                fields: vec![field("x", None)],
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert_eq!(
+            Pattern::parse("{a}{b}"),
+            Err("the fields {a} and {b} need some text between them".into())
+        );
+        assert_eq!(
+            Pattern::parse("{a}-{a}"),
+            Err("the field {a} is used twice".into())
+        );
+        assert_eq!(
+            Pattern::parse("move {amount"),
+            Err("unclosed {amount in the pattern".into())
+        );
+        assert_eq!(
+            Pattern::parse("a } b"),
+            Err("unmatched } in the pattern, use }} for a literal }".into())
+        );
+        assert_eq!(
+            Pattern::parse("{1x}"),
+            Err("{1x} does not start with a field name".into())
+        );
+        assert_eq!(
+            Pattern::parse("{xs:}"),
+            Err("the list {xs:} has an empty separator".into())
+        );
+    }
+}